@@ -1,15 +1,28 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use regex::Regex;
 use unicode_normalization::UnicodeNormalization;
 
-use format::Format;
-use syllable::Syllable;
+use format::{Erhua, Format, NeutralTone, PunctuationWidth, SurfaceTones, ToneDigits, Umlaut};
+use record::TokenRecord;
+use syllable::{Case, Syllable};
 use token::Token;
 
 use crate::error::SiphonError;
+use crate::{
+    INTERJECTION_RHYMES, TONE_COMBINING_MARKS, TONE_DIACRITIC_MAP, ZHUYIN_INITIAL_MAP,
+    ZHUYIN_RHYME_SPELLING,
+};
 
+pub mod erhua;
 pub mod format;
+pub mod inventory;
+pub mod orthography;
+pub mod punctuation;
+pub mod record;
+pub mod sandhi;
 pub mod syllable;
 pub mod token;
 
@@ -58,6 +71,109 @@ pub struct Siphon {
         verbatim_doc_comment
     )]
     latex_wrapper: String,
+    /// Digit written for the neutral tone in numbered Pinyin (aliases: neutral-tone)
+    #[arg(
+        value_enum,
+        short = 'n',
+        long = "neutral",
+        alias = "neutral-tone",
+        default_value = "5"
+    )]
+    neutral_tone: NeutralTone,
+    /// Spelling of ü in numbered Pinyin (aliases: u-style)
+    #[arg(
+        value_enum,
+        short = 'u',
+        long = "umlaut",
+        alias = "u-style",
+        default_value = "v"
+    )]
+    umlaut: Umlaut,
+    /// Placement of the tone numbers in Wade–Giles (aliases: digits)
+    #[arg(
+        value_enum,
+        short = 't',
+        long = "tone-digits",
+        alias = "digits",
+        default_value = "sup"
+    )]
+    tone_digits: ToneDigits,
+    /// Read the input as Hanzi paired with their syllables and write HTML ruby annotation
+    /// [Note] Pairs are written either as 中国(zhong1guo2), or as a line of Hanzi followed by a line of syllables.
+    #[arg(long = "ruby", default_value_t = false, verbatim_doc_comment)]
+    ruby: bool,
+    /// Write the tokens as JSON, every syllable with its parts and its rendering in every format
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    /// Apply the third tone sandhi to the IPA contours, the underlying tones are kept (aliases: tone-sandhi)
+    #[arg(
+        short = 's',
+        long = "sandhi",
+        alias = "tone-sandhi",
+        default_value_t = false
+    )]
+    sandhi: bool,
+    /// Apply the tone sandhi of yi (一) and bu (不) (aliases: yibu)
+    /// [Note] Mark a syllable with * to keep its tone (i.e. the ordinal di4 yi1*), the * is left as it is without this option.
+    #[arg(
+        long = "yi-bu",
+        alias = "yibu",
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    yi_bu: bool,
+    /// Formats showing the surface tones after the tone sandhi
+    #[arg(value_enum, long = "surface-tones", default_value = "ipa")]
+    surface_tones: SurfaceTones,
+    /// Give the neutral tones the pitch following the syllable before them in the IPA formats (aliases: contextual-neutral)
+    #[arg(
+        long = "neutral-pitch",
+        alias = "contextual-neutral",
+        default_value_t = false
+    )]
+    neutral_pitch: bool,
+    /// Handling of the erhua written as a separate neutral-tone syllable (i.e. hua1 er5, hua1'er)
+    /// [Note] In JSON, the er is folded in every format unless kept separate.
+    #[arg(
+        value_enum,
+        short = 'e',
+        long = "erhua",
+        default_value = "separate",
+        verbatim_doc_comment
+    )]
+    erhua: Erhua,
+    /// Reject the syllables which are not in the Mandarin syllable inventory (i.e. gi, fong, biu)
+    #[arg(long = "strict", default_value_t = false)]
+    strict: bool,
+    /// Join the syllables of a word, an apostrophe being written before a, o and e (i.e. xi'an, Tian'anmen) (aliases: join-words)
+    #[arg(
+        short = 'j',
+        long = "join",
+        alias = "join-words",
+        default_value_t = false
+    )]
+    join: bool,
+    /// Follow the Pinyin orthography of GB/T 16159 (aliases: gbt16159)
+    /// [Note] Sentences are capitalized and words joined; mark a proper noun with ^ (i.e. ^bei3jing1),
+    ///        a personal name with ^^ to write its surname apart (i.e. ^^wang2xiao3hong2 -> Wáng Xiǎohóng).
+    ///        Approximate numbers are hyphenated (i.e. san1 si4 tian1 -> sān-sì tiān), other compounds as in the input.
+    #[arg(
+        short = 'o',
+        long = "orthography",
+        alias = "gbt16159",
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    orthography: bool,
+    /// Width of the punctuation in the output (aliases: width)
+    #[arg(
+        value_enum,
+        short = 'p',
+        long = "punctuation",
+        alias = "width",
+        default_value = "keep"
+    )]
+    punctuation: PunctuationWidth,
     /// Text in Pinyin to convert
     /// [Attention]
     /// For the phoneme ü, you can input either 'v', the decomposed form 'ü' (u +  ̈), or the precomposed form 'ü'.
@@ -80,6 +196,20 @@ impl Default for Siphon {
         Self {
             format: Format::PinyinDiacritic,
             latex_wrapper: String::from("textsuperscript"),
+            neutral_tone: NeutralTone::default(),
+            umlaut: Umlaut::default(),
+            tone_digits: ToneDigits::default(),
+            ruby: false,
+            json: false,
+            sandhi: false,
+            yi_bu: false,
+            surface_tones: SurfaceTones::default(),
+            neutral_pitch: false,
+            erhua: Erhua::default(),
+            strict: false,
+            join: false,
+            orthography: false,
+            punctuation: PunctuationWidth::default(),
             text: vec![],
             debug: false,
         }
@@ -88,12 +218,9 @@ impl Default for Siphon {
 
 impl Siphon {
     pub fn new(text: &str) -> Self {
-        let text: Vec<String> = text
-            .split_whitespace()
-            .map(|value| value.to_string())
-            .collect();
+        // the spacing is kept as it is
         Self {
-            text,
+            text: vec![text.to_string()],
             ..Default::default()
         }
     }
@@ -105,6 +232,17 @@ impl Siphon {
     ///     - PinyinLaTeX
     ///     - IPALaTeX
     ///     - IPASuperscript
+    ///     - IPAToneLetter
+    ///     - IPATipa
+    ///     - XSampa
+    ///     - SampaC
+    ///     - PinyinNumbered
+    ///     - Zhuyin
+    ///     - WadeGiles
+    ///     - Yale
+    ///     - GwoyeuRomatzyh
+    ///     - Tongyong
+    ///     - MPS2
     pub fn format(mut self, format: Format) -> Self {
         self.format = format;
         self
@@ -112,10 +250,7 @@ impl Siphon {
 
     /// Set input pinyin text
     pub fn text(mut self, text: String) -> Self {
-        self.text = text
-            .split_whitespace()
-            .map(|value| value.to_string())
-            .collect();
+        self.text = vec![text];
         self
     }
 
@@ -126,12 +261,102 @@ impl Siphon {
     }
 
     /// Set latex wrapper command name
-    /// only valid for `Format::PinyinLaTeX` and `Format::IPALaTeX`
+    /// only valid for `Format::PinyinLaTeX`, `Format::IPALaTeX` and `Format::IPATipa`
     pub fn wrapper(mut self, wrapper: &str) -> Self {
         self.latex_wrapper = wrapper.to_string();
         self
     }
 
+    /// Set the digit of the neutral tone
+    /// only valid for `Format::PinyinNumbered`
+    pub fn neutral_tone(mut self, neutral_tone: NeutralTone) -> Self {
+        self.neutral_tone = neutral_tone;
+        self
+    }
+
+    /// Set the spelling of ü
+    /// only valid for `Format::PinyinNumbered`
+    pub fn umlaut(mut self, umlaut: Umlaut) -> Self {
+        self.umlaut = umlaut;
+        self
+    }
+
+    /// Set the placement of the tone numbers
+    /// only valid for `Format::WadeGiles`
+    pub fn tone_digits(mut self, tone_digits: ToneDigits) -> Self {
+        self.tone_digits = tone_digits;
+        self
+    }
+
+    /// Pair the Hanzi of the input with their syllables
+    pub fn ruby(mut self, ruby: bool) -> Self {
+        self.ruby = ruby;
+        self
+    }
+
+    /// Write the tokens as JSON
+    pub fn json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Apply the third tone sandhi
+    /// only visible in the IPA formats
+    pub fn sandhi(mut self, sandhi: bool) -> Self {
+        self.sandhi = sandhi;
+        self
+    }
+
+    /// Apply the tone sandhi of yi and bu
+    pub fn yi_bu(mut self, yi_bu: bool) -> Self {
+        self.yi_bu = yi_bu;
+        self
+    }
+
+    /// Set the formats showing the surface tones
+    /// only valid for `Format::PinyinDiacritic`, the IPA formats always show them
+    pub fn surface_tones(mut self, surface_tones: SurfaceTones) -> Self {
+        self.surface_tones = surface_tones;
+        self
+    }
+
+    /// Give the neutral tones the pitch following the syllable before them
+    /// only visible in the IPA formats
+    pub fn neutral_pitch(mut self, neutral_pitch: bool) -> Self {
+        self.neutral_pitch = neutral_pitch;
+        self
+    }
+
+    /// Set the handling of the erhua written as a separate syllable
+    pub fn erhua(mut self, erhua: Erhua) -> Self {
+        self.erhua = erhua;
+        self
+    }
+
+    /// Reject the syllables which are not in the Mandarin syllable inventory
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Join the syllables of a word with the apostrophe rule of GB/T 16159
+    pub fn join(mut self, join: bool) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Follow the Pinyin orthography of GB/T 16159
+    pub fn orthography(mut self, orthography: bool) -> Self {
+        self.orthography = orthography;
+        self
+    }
+
+    /// Write the punctuation as full-width CJK punctuation or as ASCII punctuation
+    pub fn punctuation(mut self, punctuation: PunctuationWidth) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
     pub fn get_format(&self) -> &Format {
         &self.format
     }
@@ -148,6 +373,62 @@ impl Siphon {
         &self.latex_wrapper
     }
 
+    pub fn get_neutral_tone(&self) -> NeutralTone {
+        self.neutral_tone
+    }
+
+    pub fn get_umlaut(&self) -> Umlaut {
+        self.umlaut
+    }
+
+    pub fn get_tone_digits(&self) -> ToneDigits {
+        self.tone_digits
+    }
+
+    pub fn get_ruby(&self) -> bool {
+        self.ruby
+    }
+
+    pub fn get_json(&self) -> bool {
+        self.json
+    }
+
+    pub fn get_sandhi(&self) -> bool {
+        self.sandhi
+    }
+
+    pub fn get_yi_bu(&self) -> bool {
+        self.yi_bu
+    }
+
+    pub fn get_surface_tones(&self) -> SurfaceTones {
+        self.surface_tones
+    }
+
+    pub fn get_neutral_pitch(&self) -> bool {
+        self.neutral_pitch
+    }
+
+    pub fn get_erhua(&self) -> Erhua {
+        self.erhua
+    }
+
+    pub fn get_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn get_join(&self) -> bool {
+        self.join
+    }
+
+    pub fn get_orthography(&self) -> bool {
+        self.orthography
+    }
+
+    pub fn get_punctuation(&self) -> PunctuationWidth {
+        self.punctuation
+    }
+
     pub fn set_format(&mut self, format: Format) {
         self.format = format
     }
@@ -160,44 +441,162 @@ impl Siphon {
         self.latex_wrapper = wrapper.to_string()
     }
 
+    pub fn set_neutral_tone(&mut self, neutral_tone: NeutralTone) {
+        self.neutral_tone = neutral_tone
+    }
+
+    pub fn set_umlaut(&mut self, umlaut: Umlaut) {
+        self.umlaut = umlaut
+    }
+
+    pub fn set_tone_digits(&mut self, tone_digits: ToneDigits) {
+        self.tone_digits = tone_digits
+    }
+
+    pub fn set_ruby(&mut self, ruby: bool) {
+        self.ruby = ruby
+    }
+
+    pub fn set_json(&mut self, json: bool) {
+        self.json = json
+    }
+
+    pub fn set_sandhi(&mut self, sandhi: bool) {
+        self.sandhi = sandhi
+    }
+
+    pub fn set_yi_bu(&mut self, yi_bu: bool) {
+        self.yi_bu = yi_bu
+    }
+
+    pub fn set_surface_tones(&mut self, surface_tones: SurfaceTones) {
+        self.surface_tones = surface_tones
+    }
+
+    pub fn set_neutral_pitch(&mut self, neutral_pitch: bool) {
+        self.neutral_pitch = neutral_pitch
+    }
+
+    pub fn set_erhua(&mut self, erhua: Erhua) {
+        self.erhua = erhua
+    }
+
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict
+    }
+
+    pub fn set_join(&mut self, join: bool) {
+        self.join = join
+    }
+
+    pub fn set_orthography(&mut self, orthography: bool) {
+        self.orthography = orthography
+    }
+
+    pub fn set_punctuation(&mut self, punctuation: PunctuationWidth) {
+        self.punctuation = punctuation
+    }
+
     /// Normalize the input text using NFC to handle combining diacritics
     fn normalize_input_to_unicode(&self) -> String {
         let input: &String = &self.text.join(" ");
         let normalized_text: String = input.nfc().collect::<String>();
         if *input != normalized_text {
-            println!("Input text has been normalized as -> {:?}", normalized_text);
+            eprintln!("Input text has been normalized as -> {:?}", normalized_text);
         }
         normalized_text
     }
 
     /// Regex pattern to match:
-    /// 1. A sequence of letters followed by an optional number (e.g., zhe4, shi)
-    /// 2. Keep spaces and punctuation in order to reproduce the same final text
+    /// 1. A sequence of letters followed by an optional number (e.g., zhe4, shi) and an optional * keeping the tone,
+    ///    or written with tone diacritics (e.g., zhè, lǘ)
+    /// 2. An interjection without vowel, with a tone number or mark (e.g., ê4, hm, ňg)
+    /// 3. A Zhuyin syllable with its tone mark (e.g., ㄓㄜˋ, ˙ㄉㄜ)
+    /// 4. Keep spaces and punctuation in order to reproduce the same final text, any other text being kept by `tokenize`
     fn get_regex(&self) -> Result<Regex, SiphonError> {
         // (?x) to make # xxxx to be ignored
         Ok(Regex::new(
             r#"(?x)
+            (?<proper>\^{1,2})?                                  # Optional mark of a proper noun, ^^ for a personal name
             (?i: # case-insensitive
                 (?<syllable>
                     (?<initial>zh|ch|sh|[bpmfdtnlgkhjqxrzcs]?)     # Optional initial (excluding y and w)
-                    (?<rime>(?:y|w)?[aeiouüv]{1,3}(?:ng|n)?(?:r)?) # Required rime
+                    (?<rime>(?:y|w)?[aeiouüvāáǎàēéěèōóǒòīíǐìūúǔùǖǘǚǜ]{1,3}(?:ng|n)?(?:r)?) # Required rime
                     (?<tone>\d?)                                   # Optional tone
                 )
+                (?<keep>\*)?                                      # Optional mark to keep the tone
             )
             (?-i)
-            |(?<space>\s+)
+            |(?<interjection>(?i:
+                (?<interjection_initial>h)?
+                (?<interjection_rhyme>(?:[mḿ]|[nńňǹ]|[êếề])[\x{304}\x{301}\x{30C}\x{300}]?g?)
+                (?<interjection_tone>\d?)
+            ))                                                    # Interjection (i.e. ê, hm, ng)
+            |(?<zhuyin>˙?(?:[ㄅ-ㄙ](?:[ㄚ-ㄩ]{1,3}|[ㄇㄋㄫ])?|[ㄚ-ㄩ]{1,3}|ㄫ)[ˉˊˇˋ]?) # Zhuyin syllable, or interjection (i.e. ㄏㄫ)
+            |(?<space>\x20)
             |(?<quote>['])
-            |(?<punctuation>[,!?.\-:"=])
+            |(?<punctuation>[,!?.\-:"=;()，。！？：；、（）“”「」『』《》〈〉…—·～])
             "#,
         )?)
     }
 
+    /// Split tone-marked vowels (e.g., ǎ, ǘ) back into plain vowels and a tone number
+    fn strip_tone_diacritics(&self, rime: &str) -> (String, Option<usize>) {
+        let mut tone: Option<usize> = None;
+        let plain: String = rime
+            .chars()
+            .map(|c| {
+                let mut buffer = [0u8; 4];
+                let vowel_marked: &str = c.encode_utf8(&mut buffer);
+                for &(vowel, diacritics) in TONE_DIACRITIC_MAP.iter() {
+                    if let Some(index) = diacritics.iter().position(|&d| d == vowel_marked) {
+                        tone = Some(index + 1);
+                        return vowel.to_string();
+                    }
+                }
+                vowel_marked.to_string()
+            })
+            .collect();
+        (plain, tone)
+    }
+
+    /// Split the tone-marked letters of an interjection (e.g., ḿ, ňg, ê̄) into the plain ones and a tone number
+    fn strip_interjection_marks(&self, rhyme: &str) -> (String, Option<usize>) {
+        let mut tone: Option<usize> = None;
+        let plain: String = rhyme
+            .nfd()
+            .filter(|c| {
+                let mut buffer = [0u8; 4];
+                match TONE_COMBINING_MARKS
+                    .iter()
+                    .position(|&mark| mark == c.encode_utf8(&mut buffer))
+                {
+                    Some(index) => {
+                        tone = Some(index + 1);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .nfc()
+            .collect();
+        (plain, tone)
+    }
+
     /// Correct rhyme parsing
     fn normalize_rhyme(&self, onset: Option<&str>, rhyme: String) -> String {
         let rhyme = match onset {
-            Some("j" | "q" | "x" | "J" | "Q" | "X") => rhyme.replace("u", "ü"),
-            Some("zh" | "ch" | "sh" | "r" | "Zh" | "Ch" | "Sh" | "R") => rhyme.replace("i", "r"),
-            Some("z" | "c" | "s" | "Z" | "S" | "C") => rhyme.replace("i", "z"),
+            // only the leading u is ü (i.e. ju, jue), not the one in iu
+            Some("j" | "q" | "x" | "J" | "Q" | "X") if rhyme.starts_with('u') => {
+                rhyme.replacen("u", "ü", 1)
+            }
+            // only the bare -i (i.e. zhi, zir), not the one in zhai or sui
+            Some("zh" | "ch" | "sh" | "r" | "Zh" | "Ch" | "Sh" | "R") if is_bare_i(&rhyme) => {
+                rhyme.replacen("i", "r", 1)
+            }
+            Some("z" | "c" | "s" | "Z" | "S" | "C") if is_bare_i(&rhyme) => {
+                rhyme.replacen("i", "z", 1)
+            }
             _ => rhyme,
         };
 
@@ -209,6 +608,113 @@ impl Siphon {
             .replace("uu", "u")
     }
 
+    /// Split a Zhuyin syllable into the Pinyin spelling of its initial and rhyme, and its tone
+    /// - the neutral tone is marked before the syllable with ˙
+    /// - the first tone may be unmarked
+    fn parse_zhuyin(&self, zhuyin: &str) -> Result<(Option<String>, String, usize), SiphonError> {
+        let (neutral, zhuyin) = match zhuyin.strip_prefix('˙') {
+            Some(rest) => (true, rest),
+            None => (false, zhuyin),
+        };
+
+        let (zhuyin, tone) = match zhuyin.chars().last() {
+            Some('ˊ') => (&zhuyin[..zhuyin.len() - 'ˊ'.len_utf8()], 2),
+            Some('ˇ') => (&zhuyin[..zhuyin.len() - 'ˇ'.len_utf8()], 3),
+            Some('ˋ') => (&zhuyin[..zhuyin.len() - 'ˋ'.len_utf8()], 4),
+            Some('ˉ') => (&zhuyin[..zhuyin.len() - 'ˉ'.len_utf8()], 1),
+            _ => (zhuyin, 1),
+        };
+        let tone = if neutral { 5 } else { tone };
+
+        // a lone ㄇ or ㄋ is the interjection m or n
+        let onset: Option<&str> = zhuyin
+            .chars()
+            .next()
+            .filter(|&first| zhuyin.chars().count() > 1 || !matches!(first, 'ㄇ' | 'ㄋ'))
+            .and_then(|first| {
+                ZHUYIN_INITIAL_MAP
+                    .entries()
+                    .find(|(_, symbol)| symbol.starts_with(first))
+                    .map(|(initial, _)| *initial)
+            });
+        let zhuyin_rhyme: &str = match onset {
+            Some(initial) => &zhuyin[ZHUYIN_INITIAL_MAP[initial].len()..],
+            None => zhuyin,
+        };
+
+        // erhua is written with a trailing ㄦ
+        let (zhuyin_rhyme, erhua) = match zhuyin_rhyme.strip_suffix('ㄦ') {
+            Some(rest) if !rest.is_empty() || onset.is_some() => (rest, "r"),
+            _ => (zhuyin_rhyme, ""),
+        };
+
+        let (after_initial, standalone) = ZHUYIN_RHYME_SPELLING
+            .get(zhuyin_rhyme)
+            .copied()
+            .ok_or_else(|| SiphonError::InvalidRhyme(zhuyin.to_string()))?;
+
+        let rhyme: String = match onset {
+            // the interjections stand alone, but for hm and hng
+            Some("h") if matches!(after_initial, "m" | "ng") => after_initial.to_string(),
+            Some(_) if INTERJECTION_RHYMES.contains(&after_initial) => {
+                return Err(SiphonError::InvalidRhyme(zhuyin.to_string()))
+            }
+            // only the sibilants and retroflexes take the empty rhyme
+            Some("zh" | "ch" | "sh" | "r" | "z" | "c" | "s") => after_initial.to_string(),
+            Some(_) if zhuyin_rhyme.is_empty() => {
+                return Err(SiphonError::InvalidRhyme(zhuyin.to_string()))
+            }
+            // ü is written as u after j, q, x
+            Some("j" | "q" | "x") => after_initial.replace("ü", "u"),
+            Some(_) => after_initial.to_string(),
+            None if standalone.is_empty() => {
+                return Err(SiphonError::InvalidRhyme(zhuyin.to_string()))
+            }
+            None => standalone.to_string(),
+        };
+
+        Ok((onset.map(String::from), rhyme + erhua, tone))
+    }
+
+    /// Whether the syllables of a word are joined with the apostrophe rule
+    fn joins_words(&self) -> bool {
+        self.join || self.orthography
+    }
+
+    /// Check the syllable spelled with `onset` and `rhyme` against `SYLLABLE_INVENTORY`
+    /// - ü may be written v, and u after j, q, x and y (i.e. lv, ju)
+    /// - a trailing r is erhua (i.e. huar)
+    fn check_inventory(
+        &self,
+        onset: Option<&str>,
+        rhyme: &str,
+        full: &str,
+    ) -> Result<(), SiphonError> {
+        let spelling: String = format!("{}{rhyme}", onset.unwrap_or_default());
+
+        if inventory::is_legal(&spelling) {
+            Ok(())
+        } else {
+            Err(SiphonError::IllegalSyllable(full.to_string()))
+        }
+    }
+
+    /// Apply the rhyme corrections required by the output format
+    fn spell_rhyme(&self, onset: Option<&str>, mut rhyme: String) -> String {
+        if self.format.needs_normalized_rhyme() {
+            rhyme = self.normalize_rhyme(onset, rhyme);
+        }
+
+        if matches!(
+            self.format,
+            Format::PinyinDiacritic | Format::PinyinNumbered
+        ) {
+            rhyme = self.correct_rhyme(onset, rhyme);
+        }
+
+        rhyme
+    }
+
     /// Correct rhyme parsing
     fn correct_rhyme(&self, onset: Option<&str>, rhyme: String) -> String {
         match onset {
@@ -224,38 +730,132 @@ impl Siphon {
         let text: String = self.normalize_input_to_unicode();
         let regex: Regex = self.get_regex()?;
 
-        for captures in regex.captures_iter(&text) {
+        // unspaced syllables are matched one by one (i.e. xianzai -> xian, zai)
+        let mut pieces: Vec<(usize, &str)> = Vec::new();
+        let mut start: usize = 0;
+        for boundary in inventory::boundaries(&text) {
+            pieces.push((start, &text[start..boundary]));
+            start = boundary;
+        }
+        pieces.push((start, &text[start..]));
+
+        let matches = pieces.iter().flat_map(|&(offset, piece)| {
+            regex
+                .captures_iter(piece)
+                .map(move |captures| (offset, captures))
+        });
+        // end of the text already tokenized
+        let mut last: usize = 0;
+        // first syllables of the personal names
+        let mut names: Vec<usize> = Vec::new();
+        for (offset, captures) in matches {
+            // the text between the matches is kept as it is
+            if let Some(matched) = captures.get(0) {
+                if offset + matched.start() > last {
+                    tokens.push(Token::Other(
+                        text[last..offset + matched.start()].to_string(),
+                    ));
+                }
+                last = offset + matched.end();
+            }
+
             if let Some(syllable) = captures.name("syllable") {
-                let onset: Option<&str> = captures
+                // converted in lowercase, the case being given back to the output
+                let mut case: Case = Case::of(syllable.as_str());
+                // without the orthography, the mark is text left as it is
+                if let Some(proper) = captures.name("proper") {
+                    if !self.orthography {
+                        tokens.push(Token::Other(proper.as_str().to_string()));
+                    } else {
+                        case = case.max(Case::Capital);
+                        if proper.len() == 2 {
+                            names.push(tokens.len());
+                        }
+                    }
+                }
+                let onset: Option<String> = captures
                     .name("initial")
                     .filter(|m| !m.as_str().is_empty())
-                    .map(|on| on.as_str());
+                    .map(|on| on.as_str().to_lowercase());
+                let onset: Option<&str> = onset.as_deref();
 
-                let mut rhyme: String =
+                let (rhyme, marked_tone): (String, Option<usize>) =
                     match captures.name("rime").filter(|m| !m.as_str().is_empty()) {
-                        Some(value) => value.as_str().to_string(),
+                        Some(value) => self.strip_tone_diacritics(value.as_str()),
                         None => return Err(SiphonError::RhymeNotFound),
                     };
+                let rhyme: String = rhyme.to_lowercase();
 
-                let tone: Option<usize> =
-                    captures.name("tone").and_then(|t| t.as_str().parse().ok());
+                // an explicit tone number wins over a diacritic
+                let tone: Option<usize> = captures
+                    .name("tone")
+                    .and_then(|t| t.as_str().parse().ok())
+                    .or(marked_tone);
 
-                if matches!(self.format, Format::IPALaTeX)
-                    | matches!(self.format, Format::IPASuperscript)
-                {
-                    rhyme = self.normalize_rhyme(onset, rhyme);
+                if self.strict {
+                    self.check_inventory(onset, &rhyme, syllable.as_str())?;
+                }
+
+                let rhyme: String = self.spell_rhyme(onset, rhyme);
+
+                let mut syllable = Syllable::new()
+                    .full_syllable(syllable.as_str())
+                    .onset(onset)
+                    .rhyme(rhyme)
+                    .tone(tone)
+                    .case(case);
+                // a surface contour set in advance is left alone by the sandhi
+                let keep: bool = captures.name("keep").is_some();
+                if keep && self.yi_bu {
+                    syllable.surface = Some(syllable.transpose_tone_value()?.to_string());
+                }
+
+                tokens.push(Token::Syllable(syllable));
+                // without the yi and bu sandhi, the mark is text left as it is
+                if keep && !self.yi_bu {
+                    tokens.push(Token::Other(String::from("*")));
+                }
+            } else if let (Some(interjection), Some(rhyme)) = (
+                captures.name("interjection"),
+                captures.name("interjection_rhyme"),
+            ) {
+                let (rhyme, marked_tone) = self.strip_interjection_marks(rhyme.as_str());
+                let rhyme: String = rhyme.to_lowercase();
+                let tone: Option<usize> = captures
+                    .name("interjection_tone")
+                    .and_then(|t| t.as_str().parse().ok())
+                    .or(marked_tone);
+                let onset: Option<String> = captures
+                    .name("interjection_initial")
+                    .map(|m| m.as_str().to_lowercase());
+                let onset: Option<&str> = onset.as_deref();
+
+                if self.strict {
+                    self.check_inventory(onset, &rhyme, interjection.as_str())?;
                 }
 
-                if matches!(self.format, Format::PinyinDiacritic) {
-                    rhyme = self.correct_rhyme(onset, rhyme);
+                let token = Token::Syllable(
+                    Syllable::new()
+                        .full_syllable(interjection.as_str())
+                        .onset(onset)
+                        .rhyme(rhyme)
+                        .tone(tone)
+                        .case(Case::of(interjection.as_str())),
+                );
+                tokens.push(token);
+            } else if let Some(zhuyin) = captures.name("zhuyin") {
+                let (onset, rhyme, tone) = self.parse_zhuyin(zhuyin.as_str())?;
+                if self.strict {
+                    self.check_inventory(onset.as_deref(), &rhyme, zhuyin.as_str())?;
                 }
+                let rhyme: String = self.spell_rhyme(onset.as_deref(), rhyme);
 
                 let token = Token::Syllable(
                     Syllable::new()
-                        .full_syllable(syllable.as_str())
+                        .full_syllable(zhuyin.as_str())
                         .onset(onset)
                         .rhyme(rhyme)
-                        .tone(tone),
+                        .tone(Some(tone)),
                 );
                 tokens.push(token);
             } else if captures.name("space").is_some() {
@@ -266,36 +866,154 @@ impl Siphon {
                 tokens.push(Token::Punctuation(punct.as_str().to_string()));
             }
         }
+        if last < text.len() {
+            tokens.push(Token::Other(text[last..].to_string()));
+        }
+        if self.orthography {
+            tokens = orthography::split_names(tokens, &names);
+        }
+
+        match self.erhua {
+            Erhua::Merge => tokens = erhua::merge(tokens),
+            Erhua::KeepPinyin if !self.format.is_pinyin() => tokens = erhua::merge(tokens),
+            _ => {}
+        }
+        if self.yi_bu {
+            sandhi::yi_bu(&mut tokens);
+        }
+        if self.sandhi {
+            sandhi::third_tone(&mut tokens);
+        }
+        if self.neutral_pitch {
+            sandhi::neutral_tone(&mut tokens);
+        }
+        if self.orthography && self.format.is_romanization() {
+            tokens = orthography::hyphenate_numbers(tokens);
+        }
+        // the apostrophe rule is the one of Hanyu Pinyin and Tongyong Pinyin
+        if self.joins_words() && (self.format.is_pinyin() || self.format == Format::Tongyong) {
+            tokens = orthography::join(tokens);
+        }
+        if self.orthography {
+            orthography::capitalize_sentences(&mut tokens);
+        }
 
         Ok(tokens)
     }
 
     pub fn transform(&self, tokens: Vec<Token>) -> Result<String, SiphonError> {
+        // whether the last full-width quotation mark was an opening one
+        let mut quote_opened: bool = false;
         let transformed: Vec<String> = tokens
             .iter()
-            .map(|tok| {
+            .enumerate()
+            .map(|(index, tok)| {
                 let (word_transformed, tone_transformed) = match tok {
                     Token::Syllable(syl) => match self.format {
+                        Format::PinyinDiacritic
+                            if self.surface_tones == SurfaceTones::Pinyin
+                                && sandhi::is_yi_bu(syl)
+                                && syl.surface_tone().is_some() =>
+                        {
+                            let surface = Syllable {
+                                tone: syl.surface_tone(),
+                                ..syl.clone()
+                            };
+                            surface
+                                .convert_to_pinyin(self.get_format(), self.get_latex_wrapper())?
+                        }
                         Format::PinyinLaTeX
                         | Format::PinyinDiacritic
                         | Format::PinyinSuperscript => {
                             syl.convert_to_pinyin(self.get_format(), self.get_latex_wrapper())?
                         }
-                        Format::IPALaTeX | Format::IPASuperscript => {
+                        Format::IPALaTeX
+                        | Format::IPASuperscript
+                        | Format::IPAToneLetter
+                        | Format::IPATipa
+                        | Format::XSampa
+                        | Format::SampaC => {
                             syl.convert_to_ipa(self.get_format(), self.get_latex_wrapper())?
                         }
+                        Format::PinyinNumbered => {
+                            syl.convert_to_numbered(self.neutral_tone, self.umlaut)?
+                        }
+                        Format::Zhuyin => {
+                            let (word, tone) = syl.convert_to_zhuyin()?;
+                            // one Zhuyin cluster per character
+                            match index.checked_sub(1).map(|i| &tokens[i]) {
+                                Some(Token::Syllable(_)) => (format!(" {word}"), tone),
+                                _ => (word, tone),
+                            }
+                        }
+                        Format::WadeGiles => syl.convert_to_wade_giles(self.tone_digits)?,
+                        Format::Yale => syl.convert_to_yale()?,
+                        Format::GwoyeuRomatzyh => syl.convert_to_gwoyeu_romatzyh()?,
+                        Format::Tongyong => syl.convert_to_tongyong()?,
+                        Format::MPS2 => syl.convert_to_mps2()?,
                     },
                     Token::Separator => match self.format {
                         // keep the separator
-                        Format::PinyinDiacritic => (String::from("'"), String::new()),
+                        Format::PinyinDiacritic | Format::Tongyong => {
+                            (String::from("'"), String::new())
+                        }
+                        // keep the separator of the joined words
+                        Format::PinyinSuperscript
+                        | Format::PinyinLaTeX
+                        | Format::PinyinNumbered
+                            if self.joins_words() =>
+                        {
+                            (String::from("'"), String::new())
+                        }
                         // remove the separator
                         Format::PinyinSuperscript
                         | Format::PinyinLaTeX
                         | Format::IPALaTeX
-                        | Format::IPASuperscript => (String::new(), String::new()),
+                        | Format::IPASuperscript
+                        | Format::IPAToneLetter
+                        | Format::IPATipa
+                        | Format::XSampa
+                        | Format::SampaC
+                        | Format::PinyinNumbered => (String::new(), String::new()),
+                        // syllables are split by spaces instead
+                        Format::Zhuyin => (String::from(" "), String::new()),
+                        // syllables of a word are hyphenated
+                        Format::WadeGiles
+                        | Format::Yale
+                        | Format::GwoyeuRomatzyh
+                        | Format::MPS2 => (String::from("-"), String::new()),
                     },
-                    Token::Punctuation(p) => (p.clone(), String::new()),
+                    Token::Punctuation(p) => {
+                        let full_width: Option<bool> = match self.punctuation {
+                            PunctuationWidth::Keep => None,
+                            PunctuationWidth::Auto => Some(
+                                self.format == Format::Zhuyin
+                                    || punctuation::is_next_to_hanzi(&tokens, index),
+                            ),
+                            PunctuationWidth::Full => Some(true),
+                            PunctuationWidth::Half => Some(false),
+                        };
+                        let p: String = match full_width {
+                            _ if punctuation::is_between_digits(&tokens, index) => p.clone(),
+                            Some(true) => punctuation::full_width(p, &mut quote_opened),
+                            Some(false) => punctuation::half_width(p),
+                            None => p.clone(),
+                        };
+                        (p, String::new())
+                    }
                     Token::Space => (String::from(" "), String::new()),
+                    Token::Other(text) => (text.clone(), String::new()),
+                };
+
+                let word_transformed: String = match tok {
+                    // the numbered Pinyin is always in lowercase
+                    Token::Syllable(syl)
+                        if self.format.is_romanization()
+                            && !matches!(self.format, Format::PinyinNumbered) =>
+                    {
+                        syl.case.apply(&word_transformed)
+                    }
+                    _ => word_transformed,
                 };
 
                 // Combine the transformed word and tone
@@ -305,4 +1023,145 @@ impl Siphon {
 
         Ok(transformed.join(""))
     }
+
+    /// Write the Hanzi of the input with their syllables as HTML ruby annotation
+    /// - every 汉字(pinyin) group is annotated, the text around is kept as is
+    /// - without any group, the Hanzi come first and the syllables follow (i.e. two parallel lines)
+    pub fn transform_ruby(&self) -> Result<String, SiphonError> {
+        let text: String = self.normalize_input_to_unicode();
+        let groups: Regex = Regex::new(r"(?<base>\p{Han}+)[(（](?<annotation>[^)）]*)[)）]")?;
+
+        if !groups.is_match(&text) {
+            let han: Regex = Regex::new(r"\p{Han}")?;
+            let split: usize = han.find_iter(&text).last().map_or(0, |m| m.end());
+            return self.annotate(text[..split].trim(), &text[split..]);
+        }
+
+        let mut output = String::new();
+        let mut last: usize = 0;
+        for captures in groups.captures_iter(&text) {
+            let (Some(group), Some(base), Some(annotation)) = (
+                captures.get(0),
+                captures.name("base"),
+                captures.name("annotation"),
+            ) else {
+                continue;
+            };
+            output.push_str(&text[last..group.start()]);
+            output.push_str(&self.annotate(base.as_str(), annotation.as_str())?);
+            last = group.end();
+        }
+        output.push_str(&text[last..]);
+
+        Ok(output)
+    }
+
+    /// Pair every Hanzi of the base with one syllable of the annotation, in order
+    fn annotate(&self, base: &str, annotation: &str) -> Result<String, SiphonError> {
+        let han: Regex = Regex::new(r"^\p{Han}$")?;
+        let is_han = |c: char| han.is_match(c.encode_utf8(&mut [0u8; 4]));
+
+        let syllables: Vec<Token> = self
+            .clone()
+            .text(annotation.to_string())
+            .tokenize()?
+            .into_iter()
+            .filter(|token| matches!(token, Token::Syllable(_)))
+            .collect();
+
+        let hanzi_count: usize = base.chars().filter(|&c| is_han(c)).count();
+        if hanzi_count != syllables.len() {
+            return Err(SiphonError::RubyMismatch(
+                base.to_string(),
+                hanzi_count,
+                annotation.trim().to_string(),
+                syllables.len(),
+            ));
+        }
+
+        let mut syllables = syllables.into_iter();
+        let mut output = String::new();
+        let mut in_ruby = false;
+        for c in base.chars() {
+            match (is_han(c), in_ruby) {
+                (true, false) => output.push_str("<ruby>"),
+                (false, true) => output.push_str("</ruby>"),
+                _ => {}
+            }
+            in_ruby = is_han(c);
+
+            output.push(c);
+            if let Some(syllable) = in_ruby.then(|| syllables.next()).flatten() {
+                output.push_str(&format!("<rt>{}</rt>", self.transform(vec![syllable])?));
+            }
+        }
+        if in_ruby {
+            output.push_str("</ruby>");
+        }
+
+        Ok(output)
+    }
+
+    /// Write the tokens of the input as a JSON array
+    /// - every token with its kind
+    /// - every syllable with its parts, IPA, contour and its rendering in every format
+    pub fn transform_json(&self) -> Result<String, SiphonError> {
+        // the normalized rhyme is the one found in `RHYME_MAP`
+        let tokens: Vec<Token> = self.clone().format(Format::IPASuperscript).tokenize()?;
+
+        // renderings by syllable, the formats joining the words having more separators
+        let mut rendered: Vec<BTreeMap<String, String>> = vec![BTreeMap::new(); tokens.len()];
+        for format in Format::value_variants() {
+            let Some(name) = format.to_possible_value() else {
+                continue;
+            };
+            // every format has to share the same tokens
+            let erhua: Erhua = match self.erhua {
+                Erhua::KeepPinyin => Erhua::Merge,
+                erhua => erhua,
+            };
+            let siphon: Siphon = self.clone().format(format.clone()).erhua(erhua);
+            // a format which can't render a syllable is left out of its renderings
+            let Ok(tokens) = siphon.tokenize() else {
+                continue;
+            };
+            let syllables = tokens
+                .into_iter()
+                .filter(|token| matches!(token, Token::Syllable(_)));
+            for (index, token) in syllables.enumerate() {
+                if let Ok(text) = siphon.transform(vec![token]) {
+                    rendered[index].insert(name.get_name().to_string(), text);
+                }
+            }
+        }
+
+        let mut rendered = rendered.into_iter();
+        let records: Vec<TokenRecord> = tokens
+            .into_iter()
+            .map(|token| {
+                Ok(match token {
+                    Token::Syllable(syl) => TokenRecord::Syllable {
+                        ipa: syl.convert_to_ipa(&Format::IPASuperscript, "")?.0,
+                        contour: syl.contour()?.to_string(),
+                        original: syl.full,
+                        initial: syl.initial,
+                        rhyme: syl.rhyme,
+                        tone: syl.tone,
+                        rendered: rendered.next().unwrap_or_default(),
+                    },
+                    Token::Punctuation(text) => TokenRecord::Punctuation { text },
+                    Token::Separator => TokenRecord::Separator,
+                    Token::Space => TokenRecord::Space,
+                    Token::Other(text) => TokenRecord::Other { text },
+                })
+            })
+            .collect::<Result<Vec<TokenRecord>>>()?;
+
+        Ok(serde_json::to_string_pretty(&records).map_err(anyhow::Error::from)?)
+    }
+}
+
+/// Whether the rhyme is the bare -i of the sibilants and retroflexes, with an optional erhua
+fn is_bare_i(rhyme: &str) -> bool {
+    matches!(rhyme, "i" | "ir" | "I" | "IR")
 }
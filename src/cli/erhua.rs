@@ -0,0 +1,50 @@
+use crate::{
+    cli::{syllable::Syllable, token::Token},
+    RHYME_MAP,
+};
+
+/// Fold every neutral-tone er into the rhyme of the syllable before it (i.e. hua1 er5 -> huar1)
+/// - the er may be split from its syllable by a space or an apostrophe
+/// - an er with a tone of its own is a word (i.e. nü3 er2), it is kept
+pub fn merge(tokens: Vec<Token>) -> Vec<Token> {
+    let mut merged: Vec<Token> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let Token::Syllable(syl) = token else {
+            merged.push(token);
+            continue;
+        };
+        if !is_neutral_er(&syl) {
+            merged.push(Token::Syllable(syl));
+            continue;
+        }
+
+        // the syllable before, with at most a space or an apostrophe in between
+        let split: usize = match merged.last() {
+            Some(Token::Space | Token::Separator) => 1,
+            _ => 0,
+        };
+        let before: Option<usize> = merged.len().checked_sub(split + 1);
+        match before.map(|index| &mut merged[index]) {
+            Some(Token::Syllable(previous)) if !is_erhua(&previous.rhyme) => {
+                previous.full.push_str(&syl.full);
+                previous.rhyme.push('r');
+                merged.truncate(merged.len() - split);
+            }
+            _ => merged.push(Token::Syllable(syl)),
+        }
+    }
+
+    merged
+}
+
+fn is_neutral_er(syl: &Syllable) -> bool {
+    syl.initial.is_none()
+        && syl.rhyme.eq_ignore_ascii_case("er")
+        && matches!(syl.tone, None | Some(0 | 5))
+}
+
+/// Whether `rhyme` is already r-coloured (i.e. huar, er), the apical rhyme of zhi, chi, shi, ri is not
+fn is_erhua(rhyme: &str) -> bool {
+    rhyme != "r" && rhyme.ends_with('r') && RHYME_MAP.contains_key(rhyme)
+}
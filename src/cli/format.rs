@@ -57,4 +57,229 @@ pub enum Format {
         verbatim_doc_comment
     )]
     IPASuperscript,
+    /// in IPA with Chao tone letters (i.e. tʂɤ˨˩˦)
+    ///    (aliases: chao, ipachao, toneletter, ipaletter)
+    #[value(
+        name = "ipaletter",
+        alias = "chao",
+        alias = "ipachao",
+        alias = "toneletter",
+        verbatim_doc_comment
+    )]
+    IPAToneLetter,
+    /// in IPA as TIPA macros with number wrapped in LaTeX command (i.e. t\textrtails{}\textramshorns{}\textsuperscript{214})
+    ///    (aliases: ipatipa, tipatex)
+    #[value(
+        name = "tipa",
+        alias = "ipatipa",
+        alias = "tipatex",
+        verbatim_doc_comment
+    )]
+    IPATipa,
+    /// in X-SAMPA with tone numbers (i.e. ts`7_214)
+    ///    (aliases: x-sampa, sampa)
+    #[value(
+        name = "xsampa",
+        alias = "x-sampa",
+        alias = "sampa",
+        verbatim_doc_comment
+    )]
+    XSampa,
+    /// in SAMPA-C with tone numbers (i.e. ts`7_214)
+    ///    (aliases: sampa-c)
+    #[value(name = "sampac", alias = "sampa-c", verbatim_doc_comment)]
+    SampaC,
+    /// in plain ASCII Pinyin with tone numbers (i.e. zhong1guo2)
+    ///    (aliases: numbered, ascii, pyascii, pinyinnumber)
+    #[value(
+        name = "pynumbered",
+        alias = "numbered",
+        alias = "ascii",
+        alias = "pyascii",
+        alias = "pinyinnumber",
+        verbatim_doc_comment
+    )]
+    PinyinNumbered,
+    /// in Zhuyin (Bopomofo) with tone marks (i.e. ㄓㄜˇ)
+    ///    (aliases: bopomofo, bpmf, zy)
+    #[value(
+        name = "zhuyin",
+        alias = "bopomofo",
+        alias = "bpmf",
+        alias = "zy",
+        verbatim_doc_comment
+    )]
+    Zhuyin,
+    /// in Wade–Giles with tone numbers (i.e. chê³)
+    ///    (aliases: wg, wade, wadegiles)
+    #[value(
+        name = "wade-giles",
+        alias = "wg",
+        alias = "wade",
+        alias = "wadegiles",
+        verbatim_doc_comment
+    )]
+    WadeGiles,
+    /// in Yale with diacritics (i.e. jě)
+    ///    (aliases: yl, yalemandarin)
+    #[value(
+        name = "yale",
+        alias = "yl",
+        alias = "yalemandarin",
+        verbatim_doc_comment
+    )]
+    Yale,
+    /// in Gwoyeu Romatzyh with tonal spelling (i.e. jee)
+    ///    (aliases: gr, gwoyeu, romatzyh)
+    #[value(
+        name = "gwoyeu-romatzyh",
+        alias = "gr",
+        alias = "gwoyeu",
+        alias = "romatzyh",
+        verbatim_doc_comment
+    )]
+    GwoyeuRomatzyh,
+    /// in Tongyong Pinyin with diacritics, the first tone unmarked (i.e. jhě)
+    ///    (aliases: ty, tongyong, tongyongpinyin)
+    #[value(
+        name = "tongyong",
+        alias = "ty",
+        alias = "tongyongpinyin",
+        verbatim_doc_comment
+    )]
+    Tongyong,
+    /// in Mandarin Phonetic Symbols II with diacritics (i.e. jě)
+    ///    (aliases: mps, mps2, mpsii)
+    #[value(name = "mps2", alias = "mps", alias = "mpsii", verbatim_doc_comment)]
+    MPS2,
+}
+
+impl Format {
+    /// Whether the format is built on the normalized rhyme (i.e. `iou`, `üan`, `z`)
+    /// instead of the rhyme as spelled in the input
+    pub fn needs_normalized_rhyme(&self) -> bool {
+        matches!(
+            self,
+            Format::IPALaTeX
+                | Format::IPASuperscript
+                | Format::IPAToneLetter
+                | Format::IPATipa
+                | Format::XSampa
+                | Format::SampaC
+                | Format::Zhuyin
+                | Format::WadeGiles
+                | Format::Yale
+                | Format::GwoyeuRomatzyh
+                | Format::Tongyong
+                | Format::MPS2
+        )
+    }
+
+    /// Whether the format is a spelling of Hanyu Pinyin
+    pub fn is_pinyin(&self) -> bool {
+        matches!(
+            self,
+            Format::PinyinDiacritic
+                | Format::PinyinSuperscript
+                | Format::PinyinLaTeX
+                | Format::PinyinNumbered
+        )
+    }
+
+    /// Whether the format is written in the Latin alphabet, with capital letters
+    pub fn is_romanization(&self) -> bool {
+        self.is_pinyin()
+            || matches!(
+                self,
+                Format::WadeGiles
+                    | Format::Yale
+                    | Format::GwoyeuRomatzyh
+                    | Format::Tongyong
+                    | Format::MPS2
+            )
+    }
+}
+
+/// Digit used for the neutral tone in `Format::PinyinNumbered`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NeutralTone {
+    /// neutral tone as 5 (i.e. de5)
+    #[default]
+    #[value(name = "5", alias = "five")]
+    Five,
+    /// neutral tone as 0 (i.e. de0)
+    #[value(name = "0", alias = "zero")]
+    Zero,
+}
+
+/// Spelling of ü in `Format::PinyinNumbered`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Umlaut {
+    /// ü as v (i.e. lv4)
+    #[default]
+    #[value(name = "v")]
+    V,
+    /// ü as u: (i.e. lu:4)
+    #[value(name = "u:", alias = "colon")]
+    UColon,
+    /// ü kept as ü (i.e. lü4)
+    #[value(name = "ü", alias = "umlaut")]
+    Umlaut,
+}
+
+/// Placement of the tone numbers in `Format::WadeGiles`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToneDigits {
+    /// tone as a superscript number (i.e. chê³)
+    #[default]
+    #[value(name = "sup", alias = "super", alias = "superscript")]
+    Superscript,
+    /// tone as an inline number (i.e. chê3)
+    #[value(name = "inline", alias = "number")]
+    Inline,
+}
+
+/// Formats showing the surface tones after tone sandhi
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceTones {
+    /// surface tones in the IPA formats only (i.e. bù shì, pu³⁵ ʂʅ⁵¹)
+    #[default]
+    #[value(name = "ipa")]
+    Ipa,
+    /// surface tones in diacritic Pinyin as well (i.e. bú shì, pu³⁵ ʂʅ⁵¹)
+    #[value(name = "pinyin", alias = "dia")]
+    Pinyin,
+}
+
+/// Width of the punctuation in the output
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PunctuationWidth {
+    /// punctuation kept as written
+    #[default]
+    #[value(name = "keep")]
+    Keep,
+    /// full-width in Zhuyin and next to Hanzi, ASCII elsewhere (i.e. 你好，nǐ hǎo,)
+    #[value(name = "auto")]
+    Auto,
+    /// full-width CJK punctuation (i.e. ，。！？)
+    #[value(name = "full", alias = "fullwidth")]
+    Full,
+    /// ASCII punctuation (i.e. ,.!?)
+    #[value(name = "half", alias = "halfwidth", alias = "ascii")]
+    Half,
+}
+
+/// Handling of the erhua written as a separate syllable (i.e. hua1 er5, hua1'er)
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Erhua {
+    /// er kept as a syllable of its own
+    #[default]
+    #[value(name = "separate", alias = "keep")]
+    Separate,
+    /// er folded into the rhyme before it in every format (i.e. huar1)
+    #[value(name = "merge")]
+    Merge,
+    /// er folded into the rhyme before it, but kept as a syllable in the Pinyin formats
+    #[value(name = "keep-pinyin")]
+    KeepPinyin,
 }
@@ -0,0 +1,114 @@
+use crate::{INTERJECTION_RHYMES, SYLLABLE_INVENTORY, TONE_DIACRITIC_MAP};
+
+/// Longest syllable spelling, with erhua (i.e. zhuangr)
+const MAX_SYLLABLE_LENGTH: usize = 7;
+
+/// Whether the toneless `spelling` is a legal Mandarin syllable of `SYLLABLE_INVENTORY`
+/// - ü may be written v, and u after j, q, x and y (i.e. lv, ju)
+/// - lüe and nüe may be written lue and nue
+/// - a trailing r is erhua (i.e. huar)
+pub fn is_legal(spelling: &str) -> bool {
+    let mut spelling: String = spelling.to_lowercase().replace('v', "ü");
+
+    if spelling.starts_with(['j', 'q', 'x', 'y']) {
+        spelling = spelling.replace('ü', "u");
+    }
+    if let Some(rest) = ["nue", "lue"]
+        .iter()
+        .find_map(|prefix| spelling.strip_prefix(prefix))
+    {
+        spelling = format!("{}üe{rest}", &spelling[..1]);
+    }
+    if spelling != "er" {
+        if let Some(base) = spelling.strip_suffix('r') {
+            spelling = base.to_string();
+        }
+    }
+
+    SYLLABLE_INVENTORY.contains(spelling.as_str())
+}
+
+/// Byte offsets where the unspaced syllables of `text` are split (i.e. xianzaiwomen -> xian|zai|wo|men)
+///
+/// Every run of letters between spaces, apostrophes, tone digits or punctuation is split
+/// into the fewest legal syllables, a syllable starting with a, o or e only opening a run
+/// (i.e. xian, not xi'an). A run which can't be split this way is left as it is.
+pub fn boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries: Vec<usize> = Vec::new();
+    let mut run: Vec<(usize, char)> = Vec::new();
+
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match plain_letter(c) {
+            Some(letter) => run.push((index, letter)),
+            None => {
+                let letters: String = run.iter().map(|&(_, letter)| letter).collect();
+                if let Some(splits) = segment(&letters) {
+                    boundaries.extend(splits.into_iter().map(|split| run[split].0));
+                }
+                run.clear();
+            }
+        }
+    }
+
+    boundaries
+}
+
+/// Char offsets splitting `run` into the fewest legal syllables, the longest ones first on a tie
+fn segment(run: &str) -> Option<Vec<usize>> {
+    let letters: Vec<char> = run.chars().collect();
+    let length: usize = letters.len();
+
+    // fewest syllables of letters[start..], and the end of the first one
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; length + 1];
+    best[length] = Some((0, length));
+
+    for start in (0..length).rev() {
+        for end in (start + 1..=(start + MAX_SYLLABLE_LENGTH).min(length)).rev() {
+            let spelling: String = letters[start..end].iter().collect();
+            let (Some((count, _)), true) = (best[end], is_segment(&spelling, start, end == length))
+            else {
+                continue;
+            };
+            if best[start].is_none_or(|(best_count, _)| count + 1 < best_count) {
+                best[start] = Some((count + 1, end));
+            }
+        }
+    }
+
+    let mut splits: Vec<usize> = Vec::new();
+    let mut start: usize = best[0]?.1;
+    while start < length {
+        splits.push(start);
+        start = best[start]?.1;
+    }
+    Some(splits)
+}
+
+/// Whether `spelling` can be a syllable of a run, given whether it opens and closes it
+fn is_segment(spelling: &str, start: usize, closing: bool) -> bool {
+    let opening: bool = start == 0;
+    // an apostrophe is required before a, o, e (i.e. xi'an)
+    if !opening && spelling.starts_with(['a', 'o', 'e']) {
+        return false;
+    }
+    // the interjections stand alone
+    if INTERJECTION_RHYMES.contains(&spelling.trim_start_matches('h')) && !(opening && closing) {
+        return false;
+    }
+    is_legal(spelling)
+}
+
+/// Lowercase letter without tone diacritic, if `c` may be part of a syllable
+fn plain_letter(c: char) -> Option<char> {
+    let lowercase: char = c.to_lowercase().next()?;
+    if lowercase.is_ascii_lowercase() || lowercase == 'ü' {
+        return Some(lowercase);
+    }
+
+    let mut buffer = [0u8; 4];
+    let marked: &str = lowercase.encode_utf8(&mut buffer);
+    TONE_DIACRITIC_MAP
+        .iter()
+        .find(|(_, diacritics)| diacritics.contains(&marked))
+        .and_then(|(vowel, _)| vowel.chars().next())
+}
@@ -0,0 +1,213 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    cli::{
+        syllable::{Case, Syllable},
+        token::Token,
+    },
+    COMPOUND_SURNAMES,
+};
+
+/// Punctuation ending a sentence
+const SENTENCE_ENDS: [&str; 6] = [".", "!", "?", "。", "！", "？"];
+
+/// Digits 1-9 with their tone, and their value (liang3 as 2 in the approximate numbers)
+const DIGITS: [(&str, usize, usize); 10] = [
+    ("yi", 1, 1),
+    ("liang", 3, 2),
+    ("er", 4, 2),
+    ("san", 1, 3),
+    ("si", 4, 4),
+    ("wu", 3, 5),
+    ("liu", 4, 6),
+    ("qi", 1, 7),
+    ("ba", 1, 8),
+    ("jiu", 3, 9),
+];
+
+/// Other numerals with their tone, ending an approximate number (i.e. shi2 for 10)
+const NUMERALS: [(&str, usize); 5] = [("ling", 2), ("shi", 2), ("bai", 3), ("qian", 1), ("wan", 4)];
+
+/// Join the syllables of the words with the apostrophe rule of GB/T 16159
+/// - a syllable starting with a, o or e inside a word is preceded by an apostrophe (i.e. xi'an, Tian'anmen)
+/// - the apostrophes written before any other syllable are removed (i.e. zhong'guo -> zhongguo)
+pub fn join(tokens: Vec<Token>) -> Vec<Token> {
+    let mut joined: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let after_syllable: bool = matches!(joined.last(), Some(Token::Syllable(_)));
+        match token {
+            Token::Separator
+                if after_syllable && matches!(tokens.peek(), Some(Token::Syllable(_))) => {}
+            Token::Syllable(syl) => {
+                if after_syllable && needs_apostrophe(&syl) {
+                    joined.push(Token::Separator);
+                }
+                joined.push(Token::Syllable(syl));
+            }
+            token => joined.push(token),
+        }
+    }
+
+    joined
+}
+
+/// Capitalize the first syllable of every sentence, a sentence being ended by `SENTENCE_ENDS`
+pub fn capitalize_sentences(tokens: &mut [Token]) {
+    let mut sentence_start: bool = true;
+
+    for token in tokens.iter_mut() {
+        match token {
+            Token::Syllable(syl) if sentence_start => {
+                syl.case = syl.case.max(Case::Capital);
+                sentence_start = false;
+            }
+            Token::Syllable(_) => {}
+            Token::Punctuation(p) if SENTENCE_ENDS.contains(&p.as_str()) => sentence_start = true,
+            _ => {}
+        }
+    }
+}
+
+/// Write the surname of every personal name apart from its given name, both capitalized
+/// (i.e. ^^wang2xiao3hong2 -> Wang Xiaohong, ^^ou1yang2xiu1 -> Ouyang Xiu)
+///
+/// `names` are the indices of the first syllables of the names; a name is the word starting there,
+/// its surname being one syllable or one of `COMPOUND_SURNAMES`.
+pub fn split_names(mut tokens: Vec<Token>, names: &[usize]) -> Vec<Token> {
+    for &start in names.iter().rev() {
+        // the syllables of the word, and the separators in between
+        let end: usize = tokens[start..]
+            .iter()
+            .position(|token| !matches!(token, Token::Syllable(_) | Token::Separator))
+            .map_or(tokens.len(), |length| start + length);
+        let syllables: Vec<usize> = (start..end)
+            .filter(|&index| matches!(tokens[index], Token::Syllable(_)))
+            .collect();
+
+        let compound: String = syllables
+            .iter()
+            .take(2)
+            .filter_map(|&index| match &tokens[index] {
+                Token::Syllable(syl) => Some(plain_spelling(syl)),
+                _ => None,
+            })
+            .collect();
+        let surname_length: usize = match COMPOUND_SURNAMES.contains(compound.as_str()) {
+            true => 2,
+            false => 1,
+        };
+        let Some(&given) = syllables.get(surname_length) else {
+            continue;
+        };
+
+        if let Token::Syllable(syl) = &mut tokens[given] {
+            syl.case = syl.case.max(Case::Capital);
+        }
+        match tokens[given - 1] {
+            Token::Separator => tokens[given - 1] = Token::Space,
+            _ => tokens.insert(given, Token::Space),
+        }
+    }
+
+    tokens
+}
+
+/// Hyphenate the approximate numbers of two neighbouring digits standing as a word
+/// (i.e. san1 si4 tian1, san1si4 tian1 -> san-si tian, qi1 ba1 ge4 -> qi-ba ge)
+///
+/// A digit next to another numeral is a part of a longer number, it is left as it is (i.e. yi1 er4 san1).
+pub fn hyphenate_numbers(tokens: Vec<Token>) -> Vec<Token> {
+    // the first digit of every approximate number, and the second one
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut index: usize = 0;
+    while index < tokens.len() {
+        // the second digit, right after the first one or after a space or an apostrophe
+        let second: usize = match tokens.get(index + 1) {
+            Some(Token::Space | Token::Separator) => index + 2,
+            _ => index + 1,
+        };
+        let approximate: bool = digit(&tokens[index])
+            .zip(tokens.get(second).and_then(digit))
+            .is_some_and(|(first, next)| next == first + 1)
+            && !is_in_number(&tokens, index.checked_sub(1), index.checked_sub(2))
+            && !is_in_number(&tokens, Some(second + 1), Some(second + 2));
+        match approximate {
+            true => {
+                pairs.push((index, second));
+                index = second + 1;
+            }
+            false => index += 1,
+        }
+    }
+
+    let mut hyphenated: Vec<Token> = Vec::with_capacity(tokens.len() + pairs.len());
+    let mut pairs = pairs.into_iter().peekable();
+    for (index, token) in tokens.into_iter().enumerate() {
+        let Some(&(first, second)) = pairs.peek() else {
+            hyphenated.push(token);
+            continue;
+        };
+        match index {
+            // the space or the apostrophe in between gives way to the hyphen
+            _ if index > first && index < second => {}
+            _ if index == first => {
+                hyphenated.push(token);
+                hyphenated.push(Token::Punctuation(String::from("-")));
+            }
+            _ if index == second => {
+                hyphenated.push(token);
+                pairs.next();
+            }
+            _ => hyphenated.push(token),
+        }
+    }
+
+    hyphenated
+}
+
+/// Whether the token at `near` joins a word or a number with the digit beside it,
+/// `beyond` being the token after it, away from the digit
+fn is_in_number(tokens: &[Token], near: Option<usize>, beyond: Option<usize>) -> bool {
+    match near.and_then(|index| tokens.get(index)) {
+        Some(Token::Syllable(_) | Token::Separator) => true,
+        Some(Token::Space) => beyond
+            .and_then(|index| tokens.get(index))
+            .is_some_and(is_numeral),
+        _ => false,
+    }
+}
+
+/// Value of a digit 1-9 (i.e. san1 -> 3)
+fn digit(token: &Token) -> Option<usize> {
+    let Token::Syllable(syl) = token else {
+        return None;
+    };
+    let spelling: String = plain_spelling(syl);
+    DIGITS
+        .iter()
+        .find(|&&(digit, tone, _)| digit == spelling && syl.tone == Some(tone))
+        .map(|&(_, _, value)| value)
+}
+
+fn is_numeral(token: &Token) -> bool {
+    digit(token).is_some()
+        || matches!(token, Token::Syllable(syl) if NUMERALS
+            .iter()
+            .any(|&(numeral, tone)| numeral == plain_spelling(syl) && syl.tone == Some(tone)))
+}
+
+/// Toneless spelling of the syllable as written, in lowercase (i.e. Ōu -> ou)
+fn plain_spelling(syl: &Syllable) -> String {
+    syl.full
+        .nfd()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Whether the syllable starts with a, o or e (i.e. an, ou, er)
+fn needs_apostrophe(syl: &Syllable) -> bool {
+    syl.initial.is_none() && syl.rhyme.to_lowercase().starts_with(['a', 'o', 'e'])
+}
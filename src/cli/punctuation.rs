@@ -0,0 +1,67 @@
+use crate::cli::token::Token;
+use crate::{FULL_WIDTH_PUNCTUATION_MAP, HALF_WIDTH_PUNCTUATION_MAP};
+
+/// Write the punctuation as full-width CJK punctuation (i.e. , -> ，)
+/// - the ASCII quotation mark is opened and closed in turn (i.e. "ni3" -> “ni3”)
+pub fn full_width(punctuation: &str, quote_opened: &mut bool) -> String {
+    if punctuation == "\"" {
+        *quote_opened = !*quote_opened;
+        return String::from(if *quote_opened { "“" } else { "”" });
+    }
+    FULL_WIDTH_PUNCTUATION_MAP
+        .get(punctuation)
+        .map_or(punctuation, |full| full)
+        .to_string()
+}
+
+/// Write the punctuation as ASCII punctuation (i.e. ， -> ,)
+pub fn half_width(punctuation: &str) -> String {
+    HALF_WIDTH_PUNCTUATION_MAP
+        .get(punctuation)
+        .map_or(punctuation, |half| half)
+        .to_string()
+}
+
+/// Whether the punctuation `tokens[index]` is written next to Hanzi, across the punctuation around it (i.e. 你好！”ni3)
+pub fn is_next_to_hanzi(tokens: &[Token], index: usize) -> bool {
+    let is_text = |token: &&Token| !matches!(token, Token::Punctuation(_));
+    let before: Option<char> = match tokens[..index].iter().rev().find(is_text) {
+        Some(Token::Other(text)) => text.chars().last(),
+        _ => None,
+    };
+    let after: Option<char> = match tokens[index + 1..].iter().find(is_text) {
+        Some(Token::Other(text)) => text.chars().next(),
+        _ => None,
+    };
+
+    before.is_some_and(is_han) || after.is_some_and(is_han)
+}
+
+/// Whether the punctuation `tokens[index]` is part of a number (i.e. 2.5, 10:30), which is left as it is
+pub fn is_between_digits(tokens: &[Token], index: usize) -> bool {
+    let before: Option<char> = index
+        .checked_sub(1)
+        .and_then(|i| other_text(tokens, i))
+        .and_then(|text| text.chars().last());
+    let after: Option<char> = other_text(tokens, index + 1).and_then(|text| text.chars().next());
+
+    before.is_some_and(|c| c.is_ascii_digit()) && after.is_some_and(|c| c.is_ascii_digit())
+}
+
+fn other_text(tokens: &[Token], index: usize) -> Option<&str> {
+    match tokens.get(index) {
+        Some(Token::Other(text)) => Some(text),
+        _ => None,
+    }
+}
+
+/// CJK Unified Ideographs, with their extensions and the compatibility ideographs
+fn is_han(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{3134F}'
+    )
+}
@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Serializable view of a `Token`, for the JSON output
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TokenRecord {
+    Syllable {
+        /// text of the syllable as written in the input
+        original: String,
+        initial: Option<String>,
+        /// rhyme normalized for the conversion (i.e. ü of ju)
+        rhyme: String,
+        tone: Option<usize>,
+        /// segments without the tone
+        ipa: String,
+        /// pitch values realised in context, in Chao numbers (i.e. 214)
+        contour: String,
+        /// the syllable rendered in every format, by format name
+        rendered: BTreeMap<String, String>,
+    },
+    Punctuation {
+        text: String,
+    },
+    Separator,
+    Space,
+    Other {
+        text: String,
+    },
+}
@@ -0,0 +1,152 @@
+use crate::cli::{syllable::Syllable, token::Token};
+
+/// Contour of a third tone before another third tone, and of yi and bu before a fourth tone
+const RISING: &str = "35";
+/// Contour of yi before any other tone
+const FALLING: &str = "51";
+/// Contour of a third tone which is not the last of its phrase
+const HALF_THIRD: &str = "21";
+
+/// Rewrite the surface contours of the third tones, the underlying tones are kept
+/// - a third tone before another third tone is rising (35), i.e. ni3 hao3
+/// - a third tone which is not the last of its phrase is a half-third (21)
+///
+/// Chains are solved inside the words (syllables not split by a space or an apostrophe) first,
+/// then across the words from left to right (i.e. zhan3lan3 guan3 -> 35 35 214, xiao3 lao3hu3 -> 21 35 214).
+/// Punctuation and the text left as it is end the phrase.
+pub fn third_tone(tokens: &mut [Token]) {
+    for phrase in tokens.split_mut(is_phrase_end) {
+        for word in phrase.split_mut(|token| !matches!(token, Token::Syllable(_))) {
+            raise_before_third(word);
+        }
+        raise_before_third(phrase);
+
+        // every third tone left but the last of the phrase
+        let last: Option<usize> = phrase
+            .iter()
+            .rposition(|token| matches!(token, Token::Syllable(_)));
+        for (index, token) in phrase.iter_mut().enumerate() {
+            if let Token::Syllable(syl) = token {
+                if Some(index) != last && is_surface_third(syl) {
+                    syl.surface = Some(String::from(HALF_THIRD));
+                }
+            }
+        }
+    }
+}
+
+/// Rewrite the surface contours of yi (一) and bu (不), the underlying tones are kept
+/// - yi1 is rising (35) before a fourth tone, falling (51) before the other tones (i.e. yi2 ding4, yi4 tian1)
+/// - bu4 is rising (35) before a fourth tone (i.e. bu2 shi4)
+///
+/// A neutral tone after them counts as the fourth tone it mostly stands for (i.e. yi2 ge5).
+/// The last syllable of a phrase is left as it is,
+/// as well as the syllables already given a surface contour (i.e. the ordinal yi1* of di4 yi1* ming2).
+pub fn yi_bu(tokens: &mut [Token]) {
+    for phrase in tokens.split_mut(is_phrase_end) {
+        let syllables: Vec<usize> = syllable_indices(phrase);
+
+        for pair in syllables.windows(2) {
+            let next_tone: Option<usize> = match &phrase[pair[1]] {
+                Token::Syllable(syl) if matches!(syl.tone, Some(0 | 5)) => Some(4),
+                Token::Syllable(syl) => syl.tone,
+                _ => None,
+            };
+            if let Token::Syllable(syl) = &mut phrase[pair[0]] {
+                if syl.surface.is_some() {
+                    continue;
+                }
+                syl.surface = match (is_yi(syl), is_bu(syl), next_tone) {
+                    (true, _, Some(4)) | (_, true, Some(4)) => Some(String::from(RISING)),
+                    (true, _, Some(1..=3)) => Some(String::from(FALLING)),
+                    _ => None,
+                };
+            }
+        }
+    }
+}
+
+/// Give the neutral tones the pitch following the syllable before them (in Chao numbers)
+/// - 2 after a first tone, 3 after a second tone, 4 after a third tone, 1 after a fourth tone
+/// - the same pitch as a neutral tone before them (i.e. wo3 men5 de5 -> 214 4 4)
+///
+/// The tone before is the surface one after the sandhi, if it matches a tone (i.e. the rising yi2 of yi1 ge5).
+/// A neutral tone opening a phrase keeps the default neutral pitch (0).
+pub fn neutral_tone(tokens: &mut [Token]) {
+    for phrase in tokens.split_mut(is_phrase_end) {
+        let mut previous: Option<String> = None;
+
+        for token in phrase.iter_mut() {
+            let Token::Syllable(syl) = token else {
+                continue;
+            };
+
+            previous = if matches!(syl.tone, Some(0 | 5)) {
+                if syl.surface.is_none() {
+                    syl.surface = previous;
+                }
+                syl.surface.clone()
+            } else {
+                match syl.surface_tone().or(syl.tone) {
+                    Some(1) => Some(String::from("2")),
+                    Some(2) => Some(String::from("3")),
+                    Some(3) => Some(String::from("4")),
+                    Some(4) => Some(String::from("1")),
+                    _ => None,
+                }
+            };
+        }
+    }
+}
+
+/// Punctuation and the text left as it is (i.e. Hanzi, a line break) end a phrase
+fn is_phrase_end(token: &Token) -> bool {
+    matches!(token, Token::Punctuation(_) | Token::Other(_))
+}
+
+/// Whether `syl` is yi1 (一) or bu4 (不), the syllables of the yi and bu sandhi
+pub fn is_yi_bu(syl: &Syllable) -> bool {
+    is_yi(syl) || is_bu(syl)
+}
+
+fn is_yi(syl: &Syllable) -> bool {
+    syl.initial.is_none()
+        && matches!(syl.rhyme.to_lowercase().as_str(), "yi" | "i")
+        && syl.tone == Some(1)
+}
+
+fn is_bu(syl: &Syllable) -> bool {
+    syl.initial
+        .as_deref()
+        .is_some_and(|initial| initial.eq_ignore_ascii_case("b"))
+        && syl.rhyme.eq_ignore_ascii_case("u")
+        && syl.tone == Some(4)
+}
+
+/// Raise every third tone followed by another third tone, from left to right
+fn raise_before_third(tokens: &mut [Token]) {
+    let syllables: Vec<usize> = syllable_indices(tokens);
+
+    for pair in syllables.windows(2) {
+        let next_is_third: bool =
+            matches!(&tokens[pair[1]], Token::Syllable(syl) if is_surface_third(syl));
+        if let Token::Syllable(syl) = &mut tokens[pair[0]] {
+            if next_is_third && is_surface_third(syl) {
+                syl.surface = Some(String::from(RISING));
+            }
+        }
+    }
+}
+
+fn is_surface_third(syl: &Syllable) -> bool {
+    syl.tone == Some(3) && syl.surface.is_none()
+}
+
+fn syllable_indices(tokens: &[Token]) -> Vec<usize> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| matches!(token, Token::Syllable(_)))
+        .map(|(index, _)| index)
+        .collect()
+}
@@ -1,14 +1,71 @@
+use unicode_normalization::UnicodeNormalization;
+
 use crate::{
-    cli::Format, error::SiphonError, INITIAL_MAP, RHYME_MAP, TONE_DIACRITIC_MAP,
-    TONE_SUPERSCRIPT_DIGITS,
+    cli::format::{NeutralTone, ToneDigits, Umlaut},
+    cli::Format,
+    error::SiphonError,
+    GWOYEU_INITIAL_MAP, GWOYEU_RHYME_MAP, INITIAL_MAP, INTERJECTION_RHYMES, MPS2_INITIAL_MAP,
+    MPS2_RHYME_MAP, RHYME_MAP, SAMPA_C_MAP, TIPA_MAP, TONE_COMBINING_MARKS, TONE_DIACRITIC_MAP,
+    TONE_LETTERS, TONE_SUPERSCRIPT_DIGITS, TONGYONG_INITIAL_MAP, TONGYONG_RHYME_MAP,
+    WADE_GILES_INITIAL_MAP, WADE_GILES_RHYME_MAP, X_SAMPA_MAP, YALE_INITIAL_MAP, YALE_RHYME_MAP,
+    ZHUYIN_INITIAL_MAP, ZHUYIN_RHYME_MAP,
 };
 
-#[derive(Debug, PartialEq, Eq, Default)]
+/// Letter case of a syllable, given back to it in the romanizations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Case {
+    /// as converted (i.e. zhōng)
+    #[default]
+    Lower,
+    /// first letter as a capital (i.e. Zhōng)
+    Capital,
+    /// every letter as a capital (i.e. ZHŌNG)
+    Upper,
+}
+
+impl Case {
+    /// Case of the letters of `text` (i.e. zhong1, Zhong1, ZHONG1)
+    pub fn of(text: &str) -> Self {
+        let mut letters = text.chars().filter(|c| c.is_alphabetic());
+        match letters.next() {
+            Some(first) if first.is_uppercase() => {
+                let mut rest = letters.peekable();
+                if rest.peek().is_some() && rest.all(char::is_uppercase) {
+                    Case::Upper
+                } else {
+                    Case::Capital
+                }
+            }
+            _ => Case::Lower,
+        }
+    }
+
+    /// Write `word` in this case (i.e. běijīng -> Běijīng, t'ien -> T'IEN)
+    pub fn apply(&self, word: &str) -> String {
+        match self {
+            Case::Lower => word.to_string(),
+            Case::Capital => {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            Case::Upper => word.to_uppercase(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Syllable {
     pub full: String,
     pub initial: Option<String>,
     pub rhyme: String,
     pub tone: Option<usize>,
+    /// contour realised in context (i.e. after tone sandhi), the underlying tone is kept in `tone`
+    pub surface: Option<String>,
+    /// letter case as written, or capitalized by the orthography (i.e. proper nouns, sentence starts)
+    pub case: Case,
 }
 
 impl Syllable {
@@ -38,11 +95,25 @@ impl Syllable {
         self
     }
 
+    pub fn surface(mut self, surface: Option<impl Into<String>>) -> Self {
+        self.surface = surface.map(|v| v.into());
+        self
+    }
+
+    pub fn case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
     /// Fully convert pinyin into IPA, but with the optional format:
     /// - LaTeX:
     ///     - \superscript{} (default) or any other customable wrapper
+    ///     - segments as TIPA macros for pdflatex
     /// - Unicode
     ///     - superscript numbers
+    ///     - Chao tone letters (the neutral tone as a dotted mid tone bar)
+    /// - ASCII
+    ///     - X-SAMPA or SAMPA-C with tone numbers after an underscore
     pub fn convert_to_ipa(
         &self,
         format: &Format,
@@ -67,22 +138,20 @@ impl Syllable {
             .to_owned();
 
         // tone part
-        let tone_to_transform: &str = if let Some(t) = self.tone {
-            match t {
-                0 | 5 => "0",
-                1 => "55",
-                2 => "35",
-                3 => "214",
-                4 => "51",
-                _ => return Err(SiphonError::TonConversionFail(self.full.clone())),
-            }
-        } else {
-            ""
-        };
+        let tone_to_transform: &str = self.contour()?;
 
         let tone_transformed: String = match format {
             Format::IPASuperscript => self.tone_to_superscript(tone_to_transform),
-            Format::IPALaTeX => {
+            Format::IPAToneLetter => self.tone_to_letters(tone_to_transform),
+            // digits are split from the segments, as 6, 7 and 9 are vowels in SAMPA
+            Format::XSampa | Format::SampaC => {
+                if !tone_to_transform.is_empty() {
+                    format!("_{tone_to_transform}")
+                } else {
+                    String::new()
+                }
+            }
+            Format::IPALaTeX | Format::IPATipa => {
                 if !tone_to_transform.is_empty() {
                     format!(r"\{latex_wrapper}{{{tone_to_transform}}}")
                 } else {
@@ -92,7 +161,54 @@ impl Syllable {
             _ => String::new(), // never reachable
         };
 
-        Ok((onset + &rhyme, tone_transformed))
+        let word_transformed: String = match format {
+            Format::XSampa | Format::SampaC => self.ipa_to_sampa(&(onset + &rhyme), format)?,
+            Format::IPATipa => self.ipa_to_tipa(&(onset + &rhyme))?,
+            _ => onset + &rhyme,
+        };
+
+        Ok((word_transformed, tone_transformed))
+    }
+
+    /// transform IPA into TIPA macros symbol by symbol
+    pub fn ipa_to_tipa(&self, ipa: &str) -> Result<String, SiphonError> {
+        let mut segments: Vec<String> = Vec::new();
+
+        for c in ipa.chars() {
+            match (c, segments.pop()) {
+                // nasalization and syllabicity as accents on the previous symbol
+                ('\u{303}', Some(previous)) => segments.push(format!(r"\~{{{previous}}}")),
+                ('\u{329}' | '\u{30D}', Some(previous)) => {
+                    segments.push(format!(r"\textsyllabic{{{previous}}}"))
+                }
+                (c, previous) => {
+                    segments.extend(previous);
+                    let macro_name = TIPA_MAP
+                        .get(&c)
+                        .ok_or_else(|| SiphonError::TipaNotFound(c.to_string()))?;
+                    segments.push(macro_name.to_string());
+                }
+            }
+        }
+
+        Ok(segments.concat())
+    }
+
+    /// transform IPA into X-SAMPA or SAMPA-C symbol by symbol
+    pub fn ipa_to_sampa(&self, ipa: &str, format: &Format) -> Result<String, SiphonError> {
+        let table = match format {
+            Format::SampaC => &SAMPA_C_MAP,
+            _ => &X_SAMPA_MAP,
+        };
+
+        ipa.chars()
+            .map(|c| {
+                table
+                    .get(&c)
+                    .copied()
+                    .ok_or_else(|| SiphonError::SampaNotFound(c.to_string()))
+            })
+            .collect()
     }
 
     /// Convert pinyin with numbers into pinyin with diacritics
@@ -136,7 +252,438 @@ impl Syllable {
         }
     }
 
-    fn transpose_tone_value(&self) -> Result<&str, SiphonError> {
+    /// Convert pinyin into lowercase ASCII pinyin with a tone number (i.e. zhong1)
+    /// - the neutral tone (and a missing tone) is written as 5 or 0
+    /// - ü is written as v, u: or ü
+    pub fn convert_to_numbered(
+        &self,
+        neutral_tone: NeutralTone,
+        umlaut: Umlaut,
+    ) -> Result<(String, String), SiphonError> {
+        let onset = self.initial.as_deref().unwrap_or_default().to_lowercase();
+        let mut rhyme = self.rhyme.to_lowercase().replace("v", "ü");
+
+        // ü is written as u after j, q, x and y
+        if matches!(onset.as_str(), "j" | "q" | "x") || rhyme.starts_with('y') {
+            rhyme = rhyme.replace("ü", "u");
+        }
+
+        let word_transformed = match umlaut {
+            Umlaut::V => format!("{}{}", onset, rhyme.replace("ü", "v").replace("ê", "e^")),
+            Umlaut::UColon => format!("{}{}", onset, rhyme.replace("ü", "u:").replace("ê", "e^")),
+            // the output ü is decomposed (u + U+0308), as in the other formats
+            Umlaut::Umlaut => format!("{}{}", onset, rhyme.replace('\u{fc}', "u\u{308}")),
+        };
+
+        let tone_transformed = match self.tone {
+            Some(t @ 1..=4) => t.to_string(),
+            Some(0 | 5) | None => match neutral_tone {
+                NeutralTone::Five => String::from("5"),
+                NeutralTone::Zero => String::from("0"),
+            },
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((word_transformed, tone_transformed))
+    }
+
+    /// Convert pinyin into Zhuyin (Bopomofo)
+    /// - tones 2-4 are marked after the syllable with ˊ, ˇ, ˋ (tone 1 is unmarked)
+    /// - the neutral tone is marked before the syllable with ˙
+    /// - erhua is written with a trailing ㄦ
+    pub fn convert_to_zhuyin(&self) -> Result<(String, String), SiphonError> {
+        // initial part
+        let onset: &str = if let Some(initial) = &self.initial {
+            ZHUYIN_INITIAL_MAP
+                .get(&initial.to_lowercase())
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?
+        } else {
+            ""
+        };
+
+        // rhyme part
+        let rhyme: String = match ZHUYIN_RHYME_MAP.get(&self.rhyme) {
+            Some(value) => value.to_string(),
+            // the syllable er itself
+            None if self.initial.is_none() && self.rhyme == "er" => String::from("ㄦ"),
+            // erhua
+            None => self
+                .rhyme
+                .strip_suffix('r')
+                .and_then(|base| ZHUYIN_RHYME_MAP.get(base))
+                .map(|value| format!("{value}ㄦ"))
+                .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?,
+        };
+
+        // tone part
+        let (neutral_mark, tone_mark) = match self.tone {
+            Some(0 | 5) => ("˙", ""),
+            Some(1) | None => ("", ""),
+            Some(2) => ("", "ˊ"),
+            Some(3) => ("", "ˇ"),
+            Some(4) => ("", "ˋ"),
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((
+            format!("{neutral_mark}{onset}{rhyme}"),
+            tone_mark.to_string(),
+        ))
+    }
+
+    /// Convert pinyin into Wade–Giles
+    /// - aspiration is marked with an apostrophe (i.e. ch'ih)
+    /// - tones 1-4 are written as superscript or inline numbers, the neutral tone is unmarked
+    pub fn convert_to_wade_giles(
+        &self,
+        tone_digits: ToneDigits,
+    ) -> Result<(String, String), SiphonError> {
+        let initial: Option<String> = self.initial.as_ref().map(|i| i.to_lowercase());
+
+        // initial part
+        let onset: &str = match initial.as_deref() {
+            // tz, tz', ss before the empty rhyme (i.e. tzŭ, ssŭ)
+            Some("z") if self.rhyme == "z" => "tz",
+            Some("c") if self.rhyme == "z" => "tz'",
+            Some("s") if self.rhyme == "z" => "ss",
+            Some(i) => WADE_GILES_INITIAL_MAP
+                .get(i)
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?,
+            None => "",
+        };
+
+        // rhyme part, erhua keeps its trailing r
+        let (rhyme, erhua): (&str, &str) = match self.rhyme.strip_suffix('r') {
+            Some(base) if WADE_GILES_RHYME_MAP.contains_key(base) => (base, "r"),
+            _ => (self.rhyme.as_str(), ""),
+        };
+        let rhyme: &str = match (initial.as_deref(), rhyme) {
+            // the syllable er itself
+            (None, "e") if erhua == "r" => return self.wade_giles_tone("êrh", tone_digits),
+            (Some("g" | "k" | "h"), "e") => "o",
+            (Some("g" | "k" | "h" | "sh"), "uo") => "uo",
+            // kuei, k'uei, but hui, shui, tui
+            (Some("g" | "k"), "ui" | "uei") => "uei",
+            (Some(_), rhyme) => {
+                WADE_GILES_RHYME_MAP
+                    .get(rhyme)
+                    .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?
+                    .0
+            }
+            (None, rhyme) => {
+                WADE_GILES_RHYME_MAP
+                    .get(rhyme)
+                    .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?
+                    .1
+            }
+        };
+
+        self.wade_giles_tone(&format!("{onset}{rhyme}{erhua}"), tone_digits)
+    }
+
+    /// Helper method to attach the tone number of Wade–Giles
+    fn wade_giles_tone(
+        &self,
+        word: &str,
+        tone_digits: ToneDigits,
+    ) -> Result<(String, String), SiphonError> {
+        let tone: String = match self.tone {
+            Some(t @ 1..=4) => t.to_string(),
+            Some(0 | 5) | None => String::new(),
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        let tone_transformed: String = match tone_digits {
+            ToneDigits::Superscript => self.tone_to_superscript(&tone),
+            ToneDigits::Inline => tone,
+        };
+
+        Ok((word.to_string(), tone_transformed))
+    }
+
+    /// Convert pinyin into Yale with diacritics
+    /// - the diacritic is placed as in pinyin, or on the last letter of a syllable without vowel
+    ///   (i.e. jr̄, dz̀)
+    /// - erhua drops a final -n or -i before the r (i.e. dyǎr), the neutral tone is unmarked
+    pub fn convert_to_yale(&self) -> Result<(String, String), SiphonError> {
+        let initial: Option<String> = self.initial.as_ref().map(|i| i.to_lowercase());
+
+        // initial part
+        let onset: &str = match initial.as_deref() {
+            Some(i) => YALE_INITIAL_MAP
+                .get(i)
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?,
+            None => "",
+        };
+
+        // rhyme part
+        let (rhyme, erhua): (&str, bool) = match self.rhyme.strip_suffix('r') {
+            Some(base) if YALE_RHYME_MAP.contains_key(base) => (base, true),
+            _ => (self.rhyme.as_str(), false),
+        };
+        let (after_initial, standalone) = YALE_RHYME_MAP
+            .get(rhyme)
+            .copied()
+            .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?;
+        let mut spelling: String = match (initial.as_deref(), rhyme) {
+            (Some("z" | "r"), "z" | "r") => String::new(),
+            // the y of sy already marks the medial
+            (Some("x"), _) => after_initial.trim_start_matches('y').to_string(),
+            (Some(_), _) => after_initial.to_string(),
+            (None, _) => standalone.to_string(),
+        };
+
+        if erhua {
+            spelling = match rhyme {
+                "z" | "r" if initial.is_some() => String::from("er"),
+                // the syllable er itself
+                "e" if initial.is_none() => String::from("er"),
+                _ => {
+                    let reduced = spelling.trim_end_matches(['n', 'i']);
+                    match spelling.ends_with("ng") || !reduced.contains(['a', 'e', 'o', 'u']) {
+                        true => format!("{spelling}r"),
+                        false => format!("{reduced}r"),
+                    }
+                }
+            };
+        }
+
+        let word: String = format!("{onset}{spelling}");
+        let word_transformed: String = match self.tone {
+            Some(t @ 1..=4) => {
+                let marked = self.place_diacritic(&word, t - 1);
+                if marked == word {
+                    format!("{word}{}", TONE_COMBINING_MARKS[t - 1])
+                } else {
+                    marked
+                }
+            }
+            Some(0 | 5) | None => word,
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((word_transformed, String::new()))
+    }
+
+    /// Convert pinyin into Gwoyeu Romatzyh, where the tone is spelled out (i.e. sheau, haw)
+    /// - the neutral tone is written in the basic form after a dot (i.e. .de)
+    /// - erhua is written with a final l (i.e. hual, naal)
+    /// - the interjections are written in the basic form in every tone (i.e. hm, è)
+    pub fn convert_to_gwoyeu_romatzyh(&self) -> Result<(String, String), SiphonError> {
+        let initial: Option<String> = self.initial.as_ref().map(|i| i.to_lowercase());
+
+        // initial part
+        let onset: &str = match initial.as_deref() {
+            Some(i) => GWOYEU_INITIAL_MAP
+                .get(i)
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?,
+            None => "",
+        };
+
+        // basic form of the rhyme
+        let basic: String = match self.rhyme.strip_suffix('r') {
+            // the syllable er itself
+            Some("e") if initial.is_none() => String::from("el"),
+            // erhua drops a final -i or -n before the l
+            Some(base) if GWOYEU_RHYME_MAP.contains_key(base) => {
+                let basic = GWOYEU_RHYME_MAP[base];
+                let reduced = basic.trim_end_matches(['i', 'n']);
+                match basic {
+                    "y" => String::from("el"),
+                    _ if basic.ends_with("ng") || !reduced.contains(['a', 'e', 'o', 'u']) => {
+                        format!("{basic}l")
+                    }
+                    _ => format!("{reduced}l"),
+                }
+            }
+            _ => GWOYEU_RHYME_MAP
+                .get(&self.rhyme)
+                .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?
+                .to_string(),
+        };
+
+        let sonorant: bool = matches!(initial.as_deref(), Some("m" | "n" | "l" | "r"));
+        let word_transformed: String = match self.tone {
+            Some(0 | 5) => format!(".{onset}{basic}"),
+            None => format!("{onset}{basic}"),
+            // the interjections have no tonal spelling
+            Some(1..=4) if INTERJECTION_RHYMES.contains(&self.rhyme.as_str()) => {
+                format!("{onset}{basic}")
+            }
+            // sonorant initials mark the first tone with h (i.e. mha)
+            Some(1) if sonorant => format!("{onset}h{basic}"),
+            // sonorant initials keep the basic form in the second tone (i.e. ma)
+            Some(2) if sonorant => format!("{onset}{basic}"),
+            Some(t @ 1..=4) => {
+                format!(
+                    "{onset}{}",
+                    self.gwoyeu_tonal_rhyme(&basic, t, initial.is_none())
+                )
+            }
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((word_transformed, String::new()))
+    }
+
+    /// Helper method to spell the tone in a Gwoyeu Romatzyh rhyme
+    fn gwoyeu_tonal_rhyme(&self, basic: &str, tone: usize, zero_initial: bool) -> String {
+        // i and u as the main vowel, or as a medial
+        let sole: bool = matches!(basic, "i" | "in" | "ing" | "u");
+        let medial: bool = !sole && basic.len() > 1 && basic.starts_with(['i', 'u']);
+        let glide: &str = if basic.starts_with('i') { "y" } else { "w" };
+
+        let tonal: String = match tone {
+            2 if sole => match basic {
+                "i" => String::from("yi"),
+                "in" => String::from("yn"),
+                "ing" => String::from("yng"),
+                _ => String::from("wu"),
+            },
+            2 if medial => format!("{glide}{}", &basic[1..]),
+            // r after the vowels (i.e. char, charng)
+            2 => {
+                let cut = basic.trim_end_matches(['n', 'g', 'l']).len();
+                format!("{}r{}", &basic[..cut], &basic[cut..])
+            }
+            3 if basic == "ie" || basic == "uo" => format!("{basic}{}", &basic[1..]),
+            // medial i, u as e, o (i.e. sheau, hoan)
+            3 if medial => {
+                let vowel = if basic.starts_with('i') { "e" } else { "o" };
+                format!("{vowel}{}", &basic[1..])
+            }
+            3 if basic == "ai" => String::from("ae"),
+            3 if basic == "au" => String::from("ao"),
+            // doubled vowel (i.e. haa, jee)
+            3 => format!("{}{basic}", &basic[..1]),
+            4 if basic.ends_with("ai") || basic.ends_with("ei") => {
+                format!("{}y", &basic[..basic.len() - 1])
+            }
+            4 if basic.ends_with("au") || basic.ends_with("ou") => {
+                format!("{}w", &basic[..basic.len() - 1])
+            }
+            4 if basic.ends_with("ng") => format!("{}q", &basic[..basic.len() - 1]),
+            4 if basic.ends_with(['n', 'l']) => format!("{basic}{}", &basic[basic.len() - 1..]),
+            4 => format!("{basic}h"),
+            _ => basic.to_string(),
+        };
+
+        // i, u without initial are written as y, w in the third and fourth tones
+        if zero_initial && (sole || medial) && matches!(tone, 3 | 4) {
+            if sole || !tonal.starts_with(['i', 'u']) {
+                format!("{glide}{tonal}")
+            } else {
+                format!("{glide}{}", &tonal[1..])
+            }
+        } else {
+            tonal
+        }
+    }
+
+    /// Convert pinyin into Tongyong Pinyin
+    /// - tones 2-4 are marked with diacritics as in pinyin, the first tone is unmarked
+    /// - the neutral tone is marked with a dot above the main vowel (i.e. dė)
+    pub fn convert_to_tongyong(&self) -> Result<(String, String), SiphonError> {
+        let initial: Option<String> = self.initial.as_ref().map(|i| i.to_lowercase());
+
+        // initial part
+        let onset: &str = match initial.as_deref() {
+            Some(i) => TONGYONG_INITIAL_MAP
+                .get(i)
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?,
+            None => "",
+        };
+
+        // rhyme part, erhua keeps its trailing r
+        let (rhyme, erhua): (&str, &str) = match self.rhyme.strip_suffix('r') {
+            Some(base) if TONGYONG_RHYME_MAP.contains_key(base) => (base, "r"),
+            _ => (self.rhyme.as_str(), ""),
+        };
+        let (after_initial, standalone) = TONGYONG_RHYME_MAP
+            .get(rhyme)
+            .copied()
+            .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?;
+        let spelling: &str = match (initial.as_deref(), rhyme) {
+            // the syllable er itself
+            (None, "e") if !erhua.is_empty() => "e",
+            // eng as ong after labials (i.e. fong)
+            (Some("b" | "p" | "m" | "f"), "eng") => "ong",
+            (Some(_), _) => after_initial,
+            (None, _) => standalone,
+        };
+
+        let word: String = format!("{onset}{spelling}{erhua}");
+        let word_transformed: String = match self.tone {
+            Some(1) | None => word,
+            Some(t @ 2..=4) => self.place_diacritic(&word, t - 1),
+            Some(0 | 5) => match self.main_vowel(&word).and_then(|vowel| word.find(vowel)) {
+                Some(pos) => format!("{}\u{307}{}", &word[..=pos], &word[pos + 1..]),
+                None => word,
+            },
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((word_transformed, String::new()))
+    }
+
+    /// Convert pinyin into Mandarin Phonetic Symbols II (MPS II)
+    /// - tones 1-4 are marked with diacritics as in pinyin, the neutral tone is unmarked
+    /// - the diacritic of a syllable without vowel is put on its last letter (i.e. jr̄, tz̀)
+    pub fn convert_to_mps2(&self) -> Result<(String, String), SiphonError> {
+        let initial: Option<String> = self.initial.as_ref().map(|i| i.to_lowercase());
+
+        // initial part
+        let onset: &str = match initial.as_deref() {
+            Some(i) => MPS2_INITIAL_MAP
+                .get(i)
+                .copied()
+                .ok_or_else(|| SiphonError::InvalidInitial(self.full.clone()))?,
+            None => "",
+        };
+
+        // rhyme part, erhua keeps its trailing r
+        let (rhyme, erhua): (&str, &str) = match self.rhyme.strip_suffix('r') {
+            Some(base) if MPS2_RHYME_MAP.contains_key(base) => (base, "r"),
+            _ => (self.rhyme.as_str(), ""),
+        };
+        let (after_initial, standalone) = MPS2_RHYME_MAP
+            .get(rhyme)
+            .copied()
+            .ok_or_else(|| SiphonError::InvalidRhyme(self.full.clone()))?;
+        let spelling: &str = match (initial.as_deref(), rhyme) {
+            // the syllable er itself
+            (None, "e") if !erhua.is_empty() => "e",
+            // erhua of the empty rhymes (i.e. sher)
+            (Some(_), "z" | "r") if !erhua.is_empty() => "e",
+            (Some("z" | "r"), "z" | "r") => "",
+            (Some(_), _) => after_initial,
+            (None, _) => standalone,
+        };
+
+        let word: String = format!("{onset}{spelling}{erhua}");
+        let word_transformed: String = match self.tone {
+            Some(t @ 1..=4) => {
+                let marked = self.place_diacritic(&word, t - 1);
+                if marked == word {
+                    format!("{word}{}", TONE_COMBINING_MARKS[t - 1])
+                } else {
+                    marked
+                }
+            }
+            Some(0 | 5) | None => word,
+            Some(_) => return Err(SiphonError::TonConversionFail(self.full.clone())),
+        };
+
+        Ok((word_transformed, String::new()))
+    }
+
+    /// pitch values of the tone in Chao numbers (i.e. 214), the neutral tone as 0
+    pub fn transpose_tone_value(&self) -> Result<&str, SiphonError> {
         match self.tone {
             Some(t) => match t {
                 0 | 5 => Ok("0"),
@@ -150,6 +697,26 @@ impl Syllable {
         }
     }
 
+    /// pitch values realised in context, the ones of the underlying tone by default
+    pub fn contour(&self) -> Result<&str, SiphonError> {
+        match &self.surface {
+            Some(surface) => Ok(surface),
+            None => self.transpose_tone_value(),
+        }
+    }
+
+    /// tone matching the surface contour, if the syllable has one (i.e. 35 -> 2)
+    /// the half-third (21) has no matching tone
+    pub fn surface_tone(&self) -> Option<usize> {
+        match self.surface.as_deref()? {
+            "55" => Some(1),
+            "35" => Some(2),
+            "214" => Some(3),
+            "51" => Some(4),
+            _ => None,
+        }
+    }
+
     /// transform 1-4 tones into actual value in superscript
     pub fn tone_to_superscript(&self, tone: &str) -> String {
         tone.chars()
@@ -163,6 +730,19 @@ impl Syllable {
             .collect()
     }
 
+    /// transform 1-4 tones into actual value in Chao tone letters
+    pub fn tone_to_letters(&self, tone: &str) -> String {
+        tone.chars()
+            .map(|c| {
+                if let Some(digit) = c.to_digit(10) {
+                    TONE_LETTERS[digit as usize]
+                } else {
+                    c // Keep non-numeric characters unchanged
+                }
+            })
+            .collect()
+    }
+
     /// transform 1-4 tones into diacritics on relevant vowels
     pub fn tone_to_diacritics(&self) -> Result<String, SiphonError> {
         // Determine the tone index (0-3) or return the original rhyme if invalid
@@ -174,47 +754,78 @@ impl Syllable {
             // Tone 0, 5, or invalid: no diacritic
         };
 
-        let rhyme = self.rhyme.clone();
+        Ok(self.place_diacritic(&self.rhyme, tone_index))
+    }
 
-        // Handle special case for "iu" first
-        if rhyme.contains("iu") {
-            return self.replace_vowel_with_diacritic("u", tone_index);
+    /// Helper method to put the diacritic of `tone_index` (0-3) on the main vowel of `spelling`
+    /// - the interjections without vowel carry it on ê, m or n (i.e. ế, ḿ, ňg)
+    fn place_diacritic(&self, spelling: &str, tone_index: usize) -> String {
+        match self.main_vowel(spelling) {
+            Some(vowel) => self.replace_vowel_with_diacritic(spelling, vowel, tone_index),
+            // the first letter of the interjection rhyme, after the h of hm, hng
+            None => {
+                let lowercase: String = spelling.to_lowercase();
+                let start: usize = match lowercase.strip_prefix('h') {
+                    _ if INTERJECTION_RHYMES.contains(&lowercase.as_str()) => 0,
+                    Some(rhyme) if INTERJECTION_RHYMES.contains(&rhyme) => 1,
+                    _ => return spelling.to_string(),
+                };
+                let mut chars = spelling.chars();
+                let onset: String = chars.by_ref().take(start).collect();
+                chars
+                    .next()
+                    .map(|first| format!("{onset}{first}{}", TONE_COMBINING_MARKS[tone_index]))
+                    .unwrap_or_default()
+                    .chars()
+                    .chain(chars)
+                    .nfc()
+                    .collect()
+            }
         }
+    }
 
+    /// Helper method to find the vowel carrying the tone in `spelling`, in lowercase
+    fn main_vowel(&self, spelling: &str) -> Option<&'static str> {
+        let spelling: &str = &spelling.to_lowercase();
         // Check priority vowels: a, e, o
-        for vowel in ["a", "e", "o"] {
-            if rhyme.contains(vowel) {
-                return self.replace_vowel_with_diacritic(vowel, tone_index);
-            }
+        if let Some(vowel) = ["a", "e", "o"]
+            .into_iter()
+            .find(|vowel| spelling.contains(vowel))
+        {
+            return Some(vowel);
         }
 
-        // Check fallback vowels: i, u, ü, v
-        for vowel in ["i", "u", "ü", "v"] {
-            if rhyme.contains(vowel) {
-                return self.replace_vowel_with_diacritic(vowel, tone_index);
-            }
+        // Handle special case for "iu"
+        if spelling.contains("iu") {
+            return Some("u");
         }
 
-        Ok(rhyme.clone())
+        // Check fallback vowels: i, u, ü, v
+        ["i", "u", "ü", "v"]
+            .into_iter()
+            .find(|vowel| spelling.contains(vowel))
     }
 
     /// Helper method to replace a vowel with its diacritic version
     fn replace_vowel_with_diacritic(
         &self,
+        spelling: &str,
         vowel: &str,
         tone_index: usize,
-    ) -> Result<String, SiphonError> {
-        if let Some(pos) = self.rhyme.find(vowel) {
-            // Search through the array for the vowel
+    ) -> String {
+        // the lowercase vowels are as long as the capitals (i.e. ü, Ü)
+        if let Some(pos) = spelling.to_lowercase().find(vowel) {
+            let written: &str = spelling.get(pos..pos + vowel.len()).unwrap_or(vowel);
+            // Search through the array for the vowel as written
             for &(vowel_row, diacritics) in TONE_DIACRITIC_MAP.iter() {
-                if vowel_row == vowel {
+                if vowel_row == written {
                     let diacritic = diacritics[tone_index];
-                    let mut result = self.rhyme.clone();
+                    let mut result = spelling.to_string();
                     result.replace_range(pos..pos + vowel.len(), diacritic);
-                    return Ok(result);
+                    return result;
                 }
             }
         }
-        Ok(self.rhyme.clone())
+        spelling.to_string()
     }
 }
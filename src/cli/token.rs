@@ -6,4 +6,6 @@ pub enum Token {
     Punctuation(String),
     Separator,
     Space,
+    /// text left as it is (i.e. Hanzi, a digit without syllable, ;)
+    Other(String),
 }
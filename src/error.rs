@@ -28,4 +28,16 @@ pub enum SiphonError {
 
     #[error("The rhyme is not valid: {0}")]
     InvalidRhyme(String),
+
+    #[error("The syllable is not in the Mandarin syllable inventory: {0}")]
+    IllegalSyllable(String),
+
+    #[error("There is no SAMPA symbol for the IPA symbol: {0}")]
+    SampaNotFound(String),
+
+    #[error("There is no TIPA macro for the IPA symbol: {0}")]
+    TipaNotFound(String),
+
+    #[error("The {1} Hanzi of {0} do not match the {3} syllables of {2}")]
+    RubyMismatch(String, usize, String, usize),
 }
@@ -4,13 +4,22 @@ pub mod error;
 pub use cli::token::Token;
 pub use cli::Siphon;
 
-use phf::phf_map;
+use phf::{phf_map, phf_set};
 
 const TONE_SUPERSCRIPT_DIGITS: [char; 6] = ['⁰', '¹', '²', '³', '⁴', '⁵'];
 
-/// Mapping of vowels to their diacritic versions for each tone
+/// Chao tone letters for the pitches 1-5, the neutral tone (0) as a dotted mid tone bar
+const TONE_LETTERS: [char; 6] = ['꜊', '˩', '˨', '˧', '˦', '˥'];
+
+/// Combining tone marks for the syllables without vowel (i.e. Yale jr̄, dz̀)
+const TONE_COMBINING_MARKS: [&str; 4] = ["\u{304}", "\u{301}", "\u{30C}", "\u{300}"];
+
+/// Rhymes of the interjections without any vowel of `TONE_DIACRITIC_MAP` (i.e. ê, hm, ng)
+const INTERJECTION_RHYMES: [&str; 4] = ["ê", "m", "n", "ng"];
+
+/// Mapping of vowels to their diacritic versions for each tone, in lowercase and in capitals
 /// 0123243
-const TONE_DIACRITIC_MAP: [(&str, [&str; 4]); 8] = [
+const TONE_DIACRITIC_MAP: [(&str, [&str; 4]); 16] = [
     ("a", ["ā", "á", "ǎ", "à"]),
     ("e", ["ē", "é", "ě", "è"]),
     ("o", ["ō", "ó", "ǒ", "ò"]),
@@ -20,8 +29,94 @@ const TONE_DIACRITIC_MAP: [(&str, [&str; 4]); 8] = [
     // ("ü", ["ǖ", "ǘ", "ǚ", "ǜ"]), // precomposed 'ü' matching to decompoased variants
     ("ü", ["ǖ", "ǘ", "ǚ", "ǜ"]), // precomposed 'ü' matching to precomposed variants
     ("v", ["ǖ", "ǘ", "ǚ", "ǜ"]), // convenient 'v'
+    // capitals
+    ("A", ["Ā", "Á", "Ǎ", "À"]),
+    ("E", ["Ē", "É", "Ě", "È"]),
+    ("O", ["Ō", "Ó", "Ǒ", "Ò"]),
+    ("I", ["Ī", "Í", "Ǐ", "Ì"]),
+    ("U", ["Ū", "Ú", "Ǔ", "Ù"]),
+    ("Ü", ["Ǖ", "Ǘ", "Ǚ", "Ǜ"]), // decompoased 'Ü'
+    ("Ü", ["Ǖ", "Ǘ", "Ǚ", "Ǜ"]), // precomposed 'Ü' matching to precomposed variants
+    ("V", ["Ǖ", "Ǘ", "Ǚ", "Ǜ"]), // convenient 'V'
 ];
 
+/// Every legal Mandarin syllable, toneless and without erhua, spelled in Hanyu Pinyin (ü after n and l only)
+pub static SYLLABLE_INVENTORY: phf::Set<&'static str> = phf_set! {
+    // zero initial, interjections
+    "a", "ai", "an", "ang", "ao", "e", "ei", "en", "eng", "er", "o", "ou", "yo", "ê", "m", "n",
+    "ng", "yi", "ya", "yao", "ye", "you", "yan", "yang", "yin", "ying", "yong", "wu", "wa", "wai",
+    "wan", "wang", "wei", "wen", "weng", "wo", "yu", "yue", "yuan", "yun",
+    // b
+    "ba", "bai", "ban", "bang", "bao", "bei", "ben", "beng", "bi", "bian", "biao", "bie", "bin",
+    "bing", "bo", "bu",
+    // p
+    "pa", "pai", "pan", "pang", "pao", "pei", "pen", "peng", "pi", "pian", "piao", "pie", "pin",
+    "ping", "po", "pou", "pu",
+    // m
+    "ma", "mai", "man", "mang", "mao", "me", "mei", "men", "meng", "mi", "mian", "miao", "mie",
+    "min", "ming", "miu", "mo", "mou", "mu",
+    // f
+    "fa", "fan", "fang", "fei", "fen", "feng", "fo", "fou", "fu",
+    // d
+    "da", "dai", "dan", "dang", "dao", "de", "dei", "den", "deng", "di", "dia", "dian", "diao",
+    "die", "ding", "diu", "dong", "dou", "du", "duan", "dui", "dun", "duo",
+    // t
+    "ta", "tai", "tan", "tang", "tao", "te", "teng", "ti", "tian", "tiao", "tie", "ting", "tong",
+    "tou", "tu", "tuan", "tui", "tun", "tuo",
+    // n
+    "na", "nai", "nan", "nang", "nao", "ne", "nei", "nen", "neng", "ni", "nian", "niang", "niao",
+    "nie", "nin", "ning", "niu", "nong", "nou", "nu", "nuan", "nuo", "nü", "nüe",
+    // l
+    "la", "lai", "lan", "lang", "lao", "le", "lei", "leng", "li", "lia", "lian", "liang", "liao",
+    "lie", "lin", "ling", "liu", "lo", "long", "lou", "lu", "luan", "lun", "luo", "lü", "lüe",
+    // g
+    "ga", "gai", "gan", "gang", "gao", "ge", "gei", "gen", "geng", "gong", "gou", "gu", "gua",
+    "guai", "guan", "guang", "gui", "gun", "guo",
+    // k
+    "ka", "kai", "kan", "kang", "kao", "ke", "kei", "ken", "keng", "kong", "kou", "ku", "kua",
+    "kuai", "kuan", "kuang", "kui", "kun", "kuo",
+    // h
+    "ha", "hai", "han", "hang", "hao", "he", "hei", "hen", "heng", "hong", "hou", "hu", "hua",
+    "huai", "huan", "huang", "hui", "hun", "huo", "hm", "hng",
+    // j
+    "ji", "jia", "jian", "jiang", "jiao", "jie", "jin", "jing", "jiong", "jiu", "ju", "juan", "jue",
+    "jun",
+    // q
+    "qi", "qia", "qian", "qiang", "qiao", "qie", "qin", "qing", "qiong", "qiu", "qu", "quan", "que",
+    "qun",
+    // x
+    "xi", "xia", "xian", "xiang", "xiao", "xie", "xin", "xing", "xiong", "xiu", "xu", "xuan", "xue",
+    "xun",
+    // zh
+    "zha", "zhai", "zhan", "zhang", "zhao", "zhe", "zhei", "zhen", "zheng", "zhi", "zhong", "zhou",
+    "zhu", "zhua", "zhuai", "zhuan", "zhuang", "zhui", "zhun", "zhuo",
+    // ch
+    "cha", "chai", "chan", "chang", "chao", "che", "chen", "cheng", "chi", "chong", "chou", "chu",
+    "chua", "chuai", "chuan", "chuang", "chui", "chun", "chuo",
+    // sh
+    "sha", "shai", "shan", "shang", "shao", "she", "shei", "shen", "sheng", "shi", "shou", "shu",
+    "shua", "shuai", "shuan", "shuang", "shui", "shun", "shuo",
+    // r
+    "ran", "rang", "rao", "re", "ren", "reng", "ri", "rong", "rou", "ru", "rua", "ruan", "rui",
+    "run", "ruo",
+    // z
+    "za", "zai", "zan", "zang", "zao", "ze", "zei", "zen", "zeng", "zi", "zong", "zou", "zu",
+    "zuan", "zui", "zun", "zuo",
+    // c
+    "ca", "cai", "can", "cang", "cao", "ce", "cen", "ceng", "ci", "cong", "cou", "cu", "cuan",
+    "cui", "cun", "cuo",
+    // s
+    "sa", "sai", "san", "sang", "sao", "se", "sen", "seng", "si", "song", "sou", "su", "suan",
+    "sui", "sun", "suo",
+};
+
+/// Compound surnames of two syllables, toneless, split from the given name as a whole (i.e. Ouyang Xiu)
+pub static COMPOUND_SURNAMES: phf::Set<&'static str> = phf_set! {
+    "ouyang", "sima", "zhuge", "shangguan", "situ", "dongfang", "huangfu", "ximen", "murong",
+    "linghu", "zhangsun", "yuwen", "gongsun", "xiahou", "duanmu", "wenren", "zhongli", "yuchi",
+    "nangong", "huyan", "tantai", "chunyu", "shentu", "xuanyuan", "zhuansun", "zuoqiu",
+};
+
 pub static INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "b"     => "p"    ,
     "p"     => "pʰ"   ,
@@ -67,6 +162,7 @@ pub static RHYME_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "ia"    => "jɑ"   ,
     "iao"   => "jɑw"  ,
     "ie"    => "jɛ"   ,
+    "io"    => "jɔ"   ,
     "iu"    => "jɤw"  ,
     "iou"   => "jɤw"  ,
     "ian"   => "jɛn"  ,
@@ -122,6 +218,7 @@ pub static RHYME_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "iar"   => "jɐʵ"  ,
     "iaor"  => "jɑʊʵ" ,
     "ier"   => "jɛʵ"  ,
+    "ior"   => "jɔʵ"  ,
     "iur"   => "jɤʊʵ" ,
     "iour"  => "jɤʊʵ" ,
     "ianr"  => "jɐʵ"  ,
@@ -151,6 +248,756 @@ pub static RHYME_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "ver"   => "ɥœʵ"  ,
     "vanr"  => "ɥɐʵ"  ,
     "vnr"   => "ɥɚ"   ,
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => "ɛ"    ,
+    "m"     => "m̩"    ,
+    "n"     => "n̩"    ,
+    "ng"    => "ŋ̍"    ,
+};
+
+/// X-SAMPA of every IPA symbol used in `INITIAL_MAP` and `RHYME_MAP`
+pub static X_SAMPA_MAP: phf::Map<char, &'static str> = phf_map! {
+    'p'     => "p"    ,
+    't'     => "t"    ,
+    'k'     => "k"    ,
+    'm'     => "m"    ,
+    'n'     => "n"    ,
+    'ŋ'     => "N"    ,
+    'f'     => "f"    ,
+    's'     => "s"    ,
+    'ɕ'     => "s\\"  ,
+    'ʂ'     => "s`"   ,
+    'ʐ'     => "z`"   ,
+    'x'     => "x"    ,
+    'l'     => "l"    ,
+    'j'     => "j"    ,
+    'w'     => "w"    ,
+    'ɥ'     => "H"    ,
+    'ʰ'     => "_h"   ,
+
+    'a'     => "a"    ,
+    'ɑ'     => "A"    ,
+    'ɐ'     => "6"    ,
+    'e'     => "e"    ,
+    'ɛ'     => "E"    ,
+    'ə'     => "@"    ,
+    'ɚ'     => "@`"   ,
+    'ɤ'     => "7"    ,
+    'ʌ'     => "V"    ,
+    'ɔ'     => "O"    ,
+    'ʊ'     => "U"    ,
+    'i'     => "i"    ,
+    'u'     => "u"    ,
+    'y'     => "y"    ,
+    'œ'     => "9"    ,
+    'ɿ'     => "z="   ,
+    'ʅ'     => "z`="  ,
+
+    'ʵ'     => "`"    , // rhotacization
+    '\u{303}' => "~"  , // nasalization
+    '\u{329}' => "="  , // syllabicity
+    '\u{30D}' => "="  , // syllabicity
+};
+
+/// SAMPA-C of every IPA symbol used in `INITIAL_MAP` and `RHYME_MAP`
+pub static SAMPA_C_MAP: phf::Map<char, &'static str> = phf_map! {
+    'p'     => "p"    ,
+    't'     => "t"    ,
+    'k'     => "k"    ,
+    'm'     => "m"    ,
+    'n'     => "n"    ,
+    'ŋ'     => "N"    ,
+    'f'     => "f"    ,
+    's'     => "s"    ,
+    'ɕ'     => "s\\"  ,
+    'ʂ'     => "s`"   ,
+    'ʐ'     => "z`"   ,
+    'x'     => "x"    ,
+    'l'     => "l"    ,
+    'j'     => "j"    ,
+    'w'     => "w"    ,
+    'ɥ'     => "H"    ,
+    'ʰ'     => "h"    ,
+
+    'a'     => "a"    ,
+    'ɑ'     => "A"    ,
+    'ɐ'     => "6"    ,
+    'e'     => "e"    ,
+    'ɛ'     => "E"    ,
+    'ə'     => "@"    ,
+    'ɚ'     => "@`"   ,
+    'ɤ'     => "7"    ,
+    'ʌ'     => "V"    ,
+    'ɔ'     => "O"    ,
+    'ʊ'     => "U"    ,
+    'i'     => "i"    ,
+    'u'     => "u"    ,
+    'y'     => "y"    ,
+    'œ'     => "9"    ,
+    'ɿ'     => "i\\"  ,
+    'ʅ'     => "i`"   ,
+
+    'ʵ'     => "`"    , // rhotacization
+    '\u{303}' => "~"  , // nasalization
+    '\u{329}' => "="  , // syllabicity
+    '\u{30D}' => "="  , // syllabicity
+};
+
+/// TIPA macros of every IPA symbol used in `INITIAL_MAP` and `RHYME_MAP`,
+/// the nasalization and the syllabicity are handled as accents on the previous symbol
+pub static TIPA_MAP: phf::Map<char, &'static str> = phf_map! {
+    'p'     => "p"                      ,
+    't'     => "t"                      ,
+    'k'     => "k"                      ,
+    'm'     => "m"                      ,
+    'n'     => "n"                      ,
+    'ŋ'     => r"\ng{}"                 ,
+    'f'     => "f"                      ,
+    's'     => "s"                      ,
+    'ɕ'     => r"\textctc{}"            ,
+    'ʂ'     => r"\textrtails{}"         ,
+    'ʐ'     => r"\textrtailz{}"         ,
+    'x'     => "x"                      ,
+    'l'     => "l"                      ,
+    'j'     => "j"                      ,
+    'w'     => "w"                      ,
+    'ɥ'     => r"\textturnh{}"          ,
+    'ʰ'     => r"\textsuperscript{h}"   ,
+
+    'a'     => "a"                      ,
+    'ɑ'     => r"\textscripta{}"        ,
+    'ɐ'     => r"\textturna{}"          ,
+    'e'     => "e"                      ,
+    'ɛ'     => r"\textepsilon{}"        ,
+    'ə'     => r"\textschwa{}"          ,
+    'ɚ'     => r"\textrhookschwa{}"     ,
+    'ɤ'     => r"\textramshorns{}"      ,
+    'ʌ'     => r"\textturnv{}"          ,
+    'ɔ'     => r"\textopeno{}"          ,
+    'ʊ'     => r"\textupsilon{}"        ,
+    'i'     => "i"                      ,
+    'u'     => "u"                      ,
+    'y'     => "y"                      ,
+    'œ'     => r"\oe{}"                 ,
+    'ɿ'     => r"\textsyllabic{z}"      ,
+    'ʅ'     => r"\textsyllabic{\textrtailz}",
+
+    'ʵ'     => r"\textrhoticity{}"      ,
+};
+
+/// ASCII punctuation of the full-width CJK punctuation
+pub static HALF_WIDTH_PUNCTUATION_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "，"    => ","    ,
+    "、"    => ","    , // enumeration comma
+    "。"    => "."    ,
+    "！"    => "!"    ,
+    "？"    => "?"    ,
+    "："    => ":"    ,
+    "；"    => ";"    ,
+    "（"    => "("    ,
+    "）"    => ")"    ,
+    "“"     => "\""   ,
+    "”"     => "\""   ,
+    "「"    => "\""   ,
+    "」"    => "\""   ,
+    "『"    => "'"    ,
+    "』"    => "'"    ,
+    "《"    => "\""   , // book title
+    "》"    => "\""   ,
+    "〈"    => "'"    ,
+    "〉"    => "'"    ,
+    "…"     => "..."  ,
+    "～"    => "~"    ,
+};
+
+/// Full-width CJK punctuation of the ASCII punctuation, the quotation mark being opened and closed
+pub static FULL_WIDTH_PUNCTUATION_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    ","     => "，"   ,
+    "."     => "。"   ,
+    "!"     => "！"   ,
+    "?"     => "？"   ,
+    ":"     => "："   ,
+    ";"     => "；"   ,
+    "("     => "（"   ,
+    ")"     => "）"   ,
+};
+
+pub static ZHUYIN_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "ㄅ"   ,
+    "p"     => "ㄆ"   ,
+    "m"     => "ㄇ"   ,
+    "f"     => "ㄈ"   ,
+    "d"     => "ㄉ"   ,
+    "t"     => "ㄊ"   ,
+    "n"     => "ㄋ"   ,
+    "l"     => "ㄌ"   ,
+    "g"     => "ㄍ"   ,
+    "k"     => "ㄎ"   ,
+    "h"     => "ㄏ"   ,
+    "j"     => "ㄐ"   ,
+    "q"     => "ㄑ"   ,
+    "x"     => "ㄒ"   ,
+    "zh"    => "ㄓ"   ,
+    "ch"    => "ㄔ"   ,
+    "sh"    => "ㄕ"   ,
+    "r"     => "ㄖ"   ,
+    "z"     => "ㄗ"   ,
+    "c"     => "ㄘ"   ,
+    "s"     => "ㄙ"   ,
+};
+
+/// Zhuyin of the (normalized) rhymes without erhua,
+/// the erhua rhymes are composed with a trailing ㄦ
+pub static ZHUYIN_RHYME_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "a"     => "ㄚ"   ,
+    "ai"    => "ㄞ"   ,
+    "ao"    => "ㄠ"   ,
+    "an"    => "ㄢ"   ,
+    "ang"   => "ㄤ"   ,
+
+    "e"     => "ㄜ"   ,
+    "ei"    => "ㄟ"   ,
+    "en"    => "ㄣ"   ,
+    "eng"   => "ㄥ"   ,
+
+    "o"     => "ㄛ"   ,
+    "uo"    => "ㄨㄛ" ,
+    "ou"    => "ㄡ"   ,
+    "ong"   => "ㄨㄥ" ,
+
+    "i"     => "ㄧ"   ,
+    "ia"    => "ㄧㄚ" ,
+    "iao"   => "ㄧㄠ" ,
+    "ie"    => "ㄧㄝ" ,
+    "io"    => "ㄧㄛ" ,
+    "iu"    => "ㄧㄡ" ,
+    "iou"   => "ㄧㄡ" ,
+    "ian"   => "ㄧㄢ" ,
+    "iang"  => "ㄧㄤ" ,
+    "in"    => "ㄧㄣ" ,
+    "ing"   => "ㄧㄥ" ,
+    "iong"  => "ㄩㄥ" ,
+
+    "u"     => "ㄨ"   ,
+    "ua"    => "ㄨㄚ" ,
+    "uai"   => "ㄨㄞ" ,
+    "uan"   => "ㄨㄢ" ,
+    "uang"  => "ㄨㄤ" ,
+    "ui"    => "ㄨㄟ" ,
+    "uei"   => "ㄨㄟ" ,
+    "un"    => "ㄨㄣ" ,
+    "uen"   => "ㄨㄣ" ,
+    "ueng"  => "ㄨㄥ" ,
+
+    "ü"     => "ㄩ"   ,
+    "v"     => "ㄩ"   ,
+    "üe"    => "ㄩㄝ" ,
+    "ve"    => "ㄩㄝ" ,
+    "üan"   => "ㄩㄢ" ,
+    "van"   => "ㄩㄢ" ,
+    "ün"    => "ㄩㄣ" ,
+    "vn"    => "ㄩㄣ" ,
+    "üen"   => "ㄩㄣ" ,
+
+    // empty rhymes after zh, ch, sh, r / z, c, s
+    "z"     => ""     ,
+    "r"     => ""     ,
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => "ㄝ"   ,
+    "m"     => "ㄇ"   ,
+    "n"     => "ㄋ"   ,
+    "ng"    => "ㄫ"   ,
+};
+
+/// Pinyin spelling of the Zhuyin rhymes: (after an initial, without initial)
+pub static ZHUYIN_RHYME_SPELLING: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
+    "ㄚ"    => ("a"   , "a"   ),
+    "ㄞ"    => ("ai"  , "ai"  ),
+    "ㄠ"    => ("ao"  , "ao"  ),
+    "ㄢ"    => ("an"  , "an"  ),
+    "ㄤ"    => ("ang" , "ang" ),
+
+    "ㄜ"    => ("e"   , "e"   ),
+    "ㄟ"    => ("ei"  , "ei"  ),
+    "ㄣ"    => ("en"  , "en"  ),
+    "ㄥ"    => ("eng" , "eng" ),
+    "ㄦ"    => ("er"  , "er"  ),
+
+    "ㄛ"    => ("o"   , "o"   ),
+    "ㄨㄛ"  => ("uo"  , "wo"  ),
+    "ㄡ"    => ("ou"  , "ou"  ),
+    "ㄨㄥ"  => ("ong" , "weng"),
+
+    "ㄧ"    => ("i"   , "yi"  ),
+    "ㄧㄚ"  => ("ia"  , "ya"  ),
+    "ㄧㄠ"  => ("iao" , "yao" ),
+    "ㄧㄝ"  => ("ie"  , "ye"  ),
+    "ㄧㄛ"  => ("io"  , "yo"  ),
+    "ㄧㄡ"  => ("iu"  , "you" ),
+    "ㄧㄢ"  => ("ian" , "yan" ),
+    "ㄧㄤ"  => ("iang", "yang"),
+    "ㄧㄣ"  => ("in"  , "yin" ),
+    "ㄧㄥ"  => ("ing" , "ying"),
+    "ㄩㄥ"  => ("iong", "yong"),
+
+    "ㄨ"    => ("u"   , "wu"  ),
+    "ㄨㄚ"  => ("ua"  , "wa"  ),
+    "ㄨㄞ"  => ("uai" , "wai" ),
+    "ㄨㄢ"  => ("uan" , "wan" ),
+    "ㄨㄤ"  => ("uang", "wang"),
+    "ㄨㄟ"  => ("ui"  , "wei" ),
+    "ㄨㄣ"  => ("un"  , "wen" ),
+
+    "ㄩ"    => ("ü"   , "yu"  ),
+    "ㄩㄝ"  => ("üe"  , "yue" ),
+    "ㄩㄢ"  => ("üan" , "yuan"),
+    "ㄩㄣ"  => ("ün"  , "yun" ),
+
+    // empty rhymes after zh, ch, sh, r / z, c, s
+    ""      => ("i"   , ""    ),
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ㄝ"    => ("ê"   , "ê"   ),
+    "ㄇ"    => ("m"   , "m"   ),
+    "ㄋ"    => ("n"   , "n"   ),
+    "ㄫ"    => ("ng"  , "ng"  ),
+};
+
+pub static WADE_GILES_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "p"    ,
+    "p"     => "p'"   ,
+    "m"     => "m"    ,
+    "f"     => "f"    ,
+    "d"     => "t"    ,
+    "t"     => "t'"   ,
+    "n"     => "n"    ,
+    "l"     => "l"    ,
+    "g"     => "k"    ,
+    "k"     => "k'"   ,
+    "h"     => "h"    ,
+    "j"     => "ch"   ,
+    "q"     => "ch'"  ,
+    "x"     => "hs"   ,
+    "zh"    => "ch"   ,
+    "ch"    => "ch'"  ,
+    "sh"    => "sh"   ,
+    "r"     => "j"    ,
+    "z"     => "ts"   ,
+    "c"     => "ts'"  ,
+    "s"     => "s"    ,
+};
+
+/// Wade–Giles spelling of the (normalized) rhymes: (after an initial, without initial)
+pub static WADE_GILES_RHYME_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
+    "a"     => ("a"   , "a"   ),
+    "ai"    => ("ai"  , "ai"  ),
+    "ao"    => ("ao"  , "ao"  ),
+    "an"    => ("an"  , "an"  ),
+    "ang"   => ("ang" , "ang" ),
+
+    "e"     => ("ê"   , "o"   ),
+    "ei"    => ("ei"  , "ei"  ),
+    "en"    => ("ên"  , "ên"  ),
+    "eng"   => ("êng" , "êng" ),
+
+    "o"     => ("o"   , "o"   ),
+    "uo"    => ("o"   , "wo"  ),
+    "ou"    => ("ou"  , "ou"  ),
+    "ong"   => ("ung" , "ung" ),
+
+    "i"     => ("i"   , "i"   ),
+    "ia"    => ("ia"  , "ya"  ),
+    "iao"   => ("iao" , "yao" ),
+    "ie"    => ("ieh" , "yeh" ),
+    "io"    => ("io"  , "yo"  ),
+    "iu"    => ("iu"  , "yu"  ),
+    "iou"   => ("iu"  , "yu"  ),
+    "ian"   => ("ien" , "yen" ),
+    "iang"  => ("iang", "yang"),
+    "in"    => ("in"  , "yin" ),
+    "ing"   => ("ing" , "ying"),
+    "iong"  => ("iung", "yung"),
+
+    "u"     => ("u"   , "wu"  ),
+    "ua"    => ("ua"  , "wa"  ),
+    "uai"   => ("uai" , "wai" ),
+    "uan"   => ("uan" , "wan" ),
+    "uang"  => ("uang", "wang"),
+    "ui"    => ("ui"  , "wei" ),
+    "uei"   => ("ui"  , "wei" ),
+    "un"    => ("un"  , "wên" ),
+    "uen"   => ("un"  , "wên" ),
+    "ueng"  => ("ung" , "wêng"),
+
+    "ü"     => ("ü"   , "yü"  ),
+    "v"     => ("ü"   , "yü"  ),
+    "üe"    => ("üeh" , "yüeh"),
+    "ve"    => ("üeh" , "yüeh"),
+    "üan"   => ("üan" , "yüan"),
+    "van"   => ("üan" , "yüan"),
+    "ün"    => ("ün"  , "yün" ),
+    "vn"    => ("ün"  , "yün" ),
+    "üen"   => ("ün"  , "yün" ),
+
+    // empty rhymes after z, c, s / zh, ch, sh, r
+    "z"     => ("ŭ"   , "ŭ"   ),
+    "r"     => ("ih"  , "ih"  ),
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => ("eh"  , "eh"  ),
+    "m"     => ("m"   , "m"   ),
+    "n"     => ("n"   , "n"   ),
+    "ng"    => ("ng"  , "ng"  ),
+};
+
+pub static YALE_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "b"    ,
+    "p"     => "p"    ,
+    "m"     => "m"    ,
+    "f"     => "f"    ,
+    "d"     => "d"    ,
+    "t"     => "t"    ,
+    "n"     => "n"    ,
+    "l"     => "l"    ,
+    "g"     => "g"    ,
+    "k"     => "k"    ,
+    "h"     => "h"    ,
+    "j"     => "j"    ,
+    "q"     => "ch"   ,
+    "x"     => "sy"   ,
+    "zh"    => "j"    ,
+    "ch"    => "ch"   ,
+    "sh"    => "sh"   ,
+    "r"     => "r"    ,
+    "z"     => "dz"   ,
+    "c"     => "ts"   ,
+    "s"     => "s"    ,
+};
+
+/// Yale spelling of the (normalized) rhymes: (after an initial, without initial)
+pub static YALE_RHYME_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
+    "a"     => ("a"   , "a"   ),
+    "ai"    => ("ai"  , "ai"  ),
+    "ao"    => ("au"  , "au"  ),
+    "an"    => ("an"  , "an"  ),
+    "ang"   => ("ang" , "ang" ),
+
+    "e"     => ("e"   , "e"   ),
+    "ei"    => ("ei"  , "ei"  ),
+    "en"    => ("en"  , "en"  ),
+    "eng"   => ("eng" , "eng" ),
+
+    "o"     => ("wo"  , "o"   ),
+    "uo"    => ("wo"  , "wo"  ),
+    "ou"    => ("ou"  , "ou"  ),
+    "ong"   => ("ung" , "ung" ),
+
+    "i"     => ("i"   , "yi"  ),
+    "ia"    => ("ya"  , "ya"  ),
+    "iao"   => ("yau" , "yau" ),
+    "ie"    => ("ye"  , "ye"  ),
+    "io"    => ("yo"  , "yo"  ),
+    "iu"    => ("you" , "you" ),
+    "iou"   => ("you" , "you" ),
+    "ian"   => ("yan" , "yan" ),
+    "iang"  => ("yang", "yang"),
+    "in"    => ("in"  , "yin" ),
+    "ing"   => ("ing" , "ying"),
+    "iong"  => ("yung", "yung"),
+
+    "u"     => ("u"   , "wu"  ),
+    "ua"    => ("wa"  , "wa"  ),
+    "uai"   => ("wai" , "wai" ),
+    "uan"   => ("wan" , "wan" ),
+    "uang"  => ("wang", "wang"),
+    "ui"    => ("wei" , "wei" ),
+    "uei"   => ("wei" , "wei" ),
+    "un"    => ("wun" , "wen" ),
+    "uen"   => ("wun" , "wen" ),
+    "ueng"  => ("weng", "weng"),
+
+    "ü"     => ("yu"  , "yu"  ),
+    "v"     => ("yu"  , "yu"  ),
+    "üe"    => ("ywe" , "ywe" ),
+    "ve"    => ("ywe" , "ywe" ),
+    "üan"   => ("ywan", "ywan"),
+    "van"   => ("ywan", "ywan"),
+    "ün"    => ("yun" , "yun" ),
+    "vn"    => ("yun" , "yun" ),
+    "üen"   => ("yun" , "yun" ),
+
+    // empty rhymes after z, c, s / zh, ch, sh, r
+    "z"     => ("z"   , "z"   ),
+    "r"     => ("r"   , "r"   ),
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => ("ê"   , "ê"   ),
+    "m"     => ("m"   , "m"   ),
+    "n"     => ("n"   , "n"   ),
+    "ng"    => ("ng"  , "ng"  ),
+};
+
+pub static GWOYEU_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "b"    ,
+    "p"     => "p"    ,
+    "m"     => "m"    ,
+    "f"     => "f"    ,
+    "d"     => "d"    ,
+    "t"     => "t"    ,
+    "n"     => "n"    ,
+    "l"     => "l"    ,
+    "g"     => "g"    ,
+    "k"     => "k"    ,
+    "h"     => "h"    ,
+    "j"     => "j"    ,
+    "q"     => "ch"   ,
+    "x"     => "sh"   ,
+    "zh"    => "j"    ,
+    "ch"    => "ch"   ,
+    "sh"    => "sh"   ,
+    "r"     => "r"    ,
+    "z"     => "tz"   ,
+    "c"     => "ts"   ,
+    "s"     => "s"    ,
+};
+
+/// Gwoyeu Romatzyh basic (first tone) form of the (normalized) rhymes
+pub static GWOYEU_RHYME_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "a"     => "a"    ,
+    "ai"    => "ai"   ,
+    "ao"    => "au"   ,
+    "an"    => "an"   ,
+    "ang"   => "ang"  ,
+
+    "e"     => "e"    ,
+    "ei"    => "ei"   ,
+    "en"    => "en"   ,
+    "eng"   => "eng"  ,
+
+    "o"     => "o"    ,
+    "uo"    => "uo"   ,
+    "ou"    => "ou"   ,
+    "ong"   => "ong"  ,
+
+    "i"     => "i"    ,
+    "ia"    => "ia"   ,
+    "iao"   => "iau"  ,
+    "ie"    => "ie"   ,
+    "io"    => "io"   ,
+    "iu"    => "iou"  ,
+    "iou"   => "iou"  ,
+    "ian"   => "ian"  ,
+    "iang"  => "iang" ,
+    "in"    => "in"   ,
+    "ing"   => "ing"  ,
+    "iong"  => "iong" ,
+
+    "u"     => "u"    ,
+    "ua"    => "ua"   ,
+    "uai"   => "uai"  ,
+    "uan"   => "uan"  ,
+    "uang"  => "uang" ,
+    "ui"    => "uei"  ,
+    "uei"   => "uei"  ,
+    "un"    => "uen"  ,
+    "uen"   => "uen"  ,
+    "ueng"  => "ueng" ,
+
+    "ü"     => "iu"   ,
+    "v"     => "iu"   ,
+    "üe"    => "iue"  ,
+    "ve"    => "iue"  ,
+    "üan"   => "iuan" ,
+    "van"   => "iuan" ,
+    "ün"    => "iun"  ,
+    "vn"    => "iun"  ,
+    "üen"   => "iun"  ,
+
+    // empty rhymes after z, c, s / zh, ch, sh, r
+    "z"     => "y"    ,
+    "r"     => "y"    ,
+
+    // Interjections (i.e. ê, m, hm, ng, hng), without tonal spelling
+    "ê"     => "è"    ,
+    "m"     => "m"    ,
+    "n"     => "n"    ,
+    "ng"    => "ng"   ,
+};
+
+pub static TONGYONG_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "b"    ,
+    "p"     => "p"    ,
+    "m"     => "m"    ,
+    "f"     => "f"    ,
+    "d"     => "d"    ,
+    "t"     => "t"    ,
+    "n"     => "n"    ,
+    "l"     => "l"    ,
+    "g"     => "g"    ,
+    "k"     => "k"    ,
+    "h"     => "h"    ,
+    "j"     => "j"    ,
+    "q"     => "c"    ,
+    "x"     => "s"    ,
+    "zh"    => "jh"   ,
+    "ch"    => "ch"   ,
+    "sh"    => "sh"   ,
+    "r"     => "r"    ,
+    "z"     => "z"    ,
+    "c"     => "c"    ,
+    "s"     => "s"    ,
+};
+
+/// Tongyong Pinyin spelling of the (normalized) rhymes: (after an initial, without initial)
+pub static TONGYONG_RHYME_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
+    "a"     => ("a"   , "a"   ),
+    "ai"    => ("ai"  , "ai"  ),
+    "ao"    => ("ao"  , "ao"  ),
+    "an"    => ("an"  , "an"  ),
+    "ang"   => ("ang" , "ang" ),
+
+    "e"     => ("e"   , "e"   ),
+    "ei"    => ("ei"  , "ei"  ),
+    "en"    => ("en"  , "en"  ),
+    "eng"   => ("eng" , "eng" ),
+
+    "o"     => ("o"   , "o"   ),
+    "uo"    => ("uo"  , "wo"  ),
+    "ou"    => ("ou"  , "ou"  ),
+    "ong"   => ("ong" , "ong" ),
+
+    "i"     => ("i"   , "yi"  ),
+    "ia"    => ("ia"  , "ya"  ),
+    "iao"   => ("iao" , "yao" ),
+    "ie"    => ("ie"  , "ye"  ),
+    "io"    => ("io"  , "yo"  ),
+    "iu"    => ("iou" , "you" ),
+    "iou"   => ("iou" , "you" ),
+    "ian"   => ("ian" , "yan" ),
+    "iang"  => ("iang", "yang"),
+    "in"    => ("in"  , "yin" ),
+    "ing"   => ("ing" , "ying"),
+    "iong"  => ("yong", "yong"),
+
+    "u"     => ("u"   , "wu"  ),
+    "ua"    => ("ua"  , "wa"  ),
+    "uai"   => ("uai" , "wai" ),
+    "uan"   => ("uan" , "wan" ),
+    "uang"  => ("uang", "wang"),
+    "ui"    => ("uei" , "wei" ),
+    "uei"   => ("uei" , "wei" ),
+    "un"    => ("un"  , "wun" ),
+    "uen"   => ("un"  , "wun" ),
+    "ueng"  => ("ong" , "wong"),
+
+    "ü"     => ("yu"  , "yu"  ),
+    "v"     => ("yu"  , "yu"  ),
+    "üe"    => ("yue" , "yue" ),
+    "ve"    => ("yue" , "yue" ),
+    "üan"   => ("yuan", "yuan"),
+    "van"   => ("yuan", "yuan"),
+    "ün"    => ("yun" , "yun" ),
+    "vn"    => ("yun" , "yun" ),
+    "üen"   => ("yun" , "yun" ),
+
+    // empty rhymes after z, c, s / zh, ch, sh, r
+    "z"     => ("ih"  , "ih"  ),
+    "r"     => ("ih"  , "ih"  ),
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => ("ê"   , "ê"   ),
+    "m"     => ("m"   , "m"   ),
+    "n"     => ("n"   , "n"   ),
+    "ng"    => ("ng"  , "ng"  ),
+};
+
+pub static MPS2_INITIAL_MAP: phf::Map<&'static str, &'static str> = phf_map! {
+    "b"     => "b"    ,
+    "p"     => "p"    ,
+    "m"     => "m"    ,
+    "f"     => "f"    ,
+    "d"     => "d"    ,
+    "t"     => "t"    ,
+    "n"     => "n"    ,
+    "l"     => "l"    ,
+    "g"     => "g"    ,
+    "k"     => "k"    ,
+    "h"     => "h"    ,
+    "j"     => "j"    ,
+    "q"     => "ch"   ,
+    "x"     => "sh"   ,
+    "zh"    => "j"    ,
+    "ch"    => "ch"   ,
+    "sh"    => "sh"   ,
+    "r"     => "r"    ,
+    "z"     => "tz"   ,
+    "c"     => "ts"   ,
+    "s"     => "s"    ,
+};
+
+/// MPS II spelling of the (normalized) rhymes: (after an initial, without initial)
+pub static MPS2_RHYME_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
+    "a"     => ("a"   , "a"   ),
+    "ai"    => ("ai"  , "ai"  ),
+    "ao"    => ("au"  , "au"  ),
+    "an"    => ("an"  , "an"  ),
+    "ang"   => ("ang" , "ang" ),
+
+    "e"     => ("e"   , "e"   ),
+    "ei"    => ("ei"  , "ei"  ),
+    "en"    => ("en"  , "en"  ),
+    "eng"   => ("eng" , "eng" ),
+
+    "o"     => ("o"   , "o"   ),
+    "uo"    => ("uo"  , "wo"  ),
+    "ou"    => ("ou"  , "ou"  ),
+    "ong"   => ("ung" , "ung" ),
+
+    "i"     => ("i"   , "yi"  ),
+    "ia"    => ("ia"  , "ya"  ),
+    "iao"   => ("iau" , "yau" ),
+    "ie"    => ("ie"  , "ye"  ),
+    "io"    => ("io"  , "yo"  ),
+    "iu"    => ("iou" , "you" ),
+    "iou"   => ("iou" , "you" ),
+    "ian"   => ("ian" , "yan" ),
+    "iang"  => ("iang", "yang"),
+    "in"    => ("in"  , "yin" ),
+    "ing"   => ("ing" , "ying"),
+    "iong"  => ("iung", "yung"),
+
+    "u"     => ("u"   , "wu"  ),
+    "ua"    => ("ua"  , "wa"  ),
+    "uai"   => ("uai" , "wai" ),
+    "uan"   => ("uan" , "wan" ),
+    "uang"  => ("uang", "wang"),
+    "ui"    => ("uei" , "wei" ),
+    "uei"   => ("uei" , "wei" ),
+    "un"    => ("uen" , "wen" ),
+    "uen"   => ("uen" , "wen" ),
+    "ueng"  => ("ung" , "weng"),
+
+    "ü"     => ("iu"  , "yu"  ),
+    "v"     => ("iu"  , "yu"  ),
+    "üe"    => ("iue" , "yue" ),
+    "ve"    => ("iue" , "yue" ),
+    "üan"   => ("iuan", "yuan"),
+    "van"   => ("iuan", "yuan"),
+    "ün"    => ("iun" , "yun" ),
+    "vn"    => ("iun" , "yun" ),
+    "üen"   => ("iun" , "yun" ),
+
+    // empty rhymes after z, c, s / zh, ch, sh, r
+    "z"     => ("z"   , "z"   ),
+    "r"     => ("r"   , "r"   ),
+
+    // Interjections (i.e. ê, m, hm, ng, hng)
+    "ê"     => ("ê"   , "ê"   ),
+    "m"     => ("m"   , "m"   ),
+    "n"     => ("n"   , "n"   ),
+    "ng"    => ("ng"  , "ng"  ),
 };
 
 // const INITIAL_MAPPING: [(&str, &str); 21] = [
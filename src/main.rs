@@ -4,6 +4,17 @@ use siphon::{Siphon, Token};
 
 fn main() -> Result<()> {
     let siphon: Siphon = Siphon::parse();
+
+    if siphon.get_ruby() {
+        println!("{}", siphon.transform_ruby()?);
+        return Ok(());
+    }
+
+    if siphon.get_json() {
+        println!("{}", siphon.transform_json()?);
+        return Ok(());
+    }
+
     let tokens: Vec<Token> = siphon.tokenize()?;
 
     if siphon.get_debug() {
@@ -1,3 +1,22 @@
+use siphon::{error::SiphonError, Siphon, Token};
+use unicode_normalization::UnicodeNormalization;
+
+/// Tokenize and transform the text of `builder`
+fn try_convert(builder: Siphon) -> Result<String, SiphonError> {
+    let tokens: Vec<Token> = builder.tokenize()?;
+    builder.transform(tokens)
+}
+
+/// Tokenize and transform the text of `builder`, which has to succeed
+fn convert(builder: Siphon) -> String {
+    try_convert(builder).unwrap()
+}
+
+/// Same as `convert`, with the output composed (i.e. ü, ê)
+fn convert_nfc(builder: Siphon) -> String {
+    convert(builder).nfc().collect()
+}
+
 #[cfg(test)]
 mod syllabel_test {
     use siphon::cli::format::Format;
@@ -10,6 +29,7 @@ mod syllabel_test {
             initial: Some(String::from("zh")),
             rhyme: String::from("e"),
             tone: Some(1),
+            ..Default::default()
         };
 
         let test_cases = [(0, "zhe"), (1, "zhē"), (2, "zhé"), (3, "zhě"), (4, "zhè")];
@@ -32,6 +52,17 @@ mod syllabel_test {
         assert_eq!(syllable.tone_to_superscript("51"), "⁵¹");
     }
 
+    #[test]
+    fn test_tone_to_letters() {
+        let syllable = Syllable::default();
+
+        assert_eq!(syllable.tone_to_letters("0"), "꜊");
+        assert_eq!(syllable.tone_to_letters("55"), "˥˥");
+        assert_eq!(syllable.tone_to_letters("35"), "˧˥");
+        assert_eq!(syllable.tone_to_letters("214"), "˨˩˦");
+        assert_eq!(syllable.tone_to_letters("51"), "˥˩");
+    }
+
     #[test]
     fn test_convert_to_pinyin() {
         let mut syllable = Syllable {
@@ -39,6 +70,7 @@ mod syllabel_test {
             initial: Some(String::from("d")),
             rhyme: String::from("iu"),
             tone: None, // Start with no tone
+            ..Default::default()
         };
 
         let test_cases = [(0, "diu"), (1, "diū"), (2, "diú"), (3, "diǔ"), (4, "diù")];
@@ -118,6 +150,7 @@ mod syllabel_test {
                                     result.err()
                                 );
                             }
+                            _ => unreachable!(),
                         }
                     }
                 }
@@ -304,6 +337,7 @@ mod syllabel_test {
                         format
                     );
                 }
+                _ => unreachable!(),
             }
         }
     }
@@ -394,7 +428,8 @@ mod cli_test {
                     full: "an1".to_string(),
                     initial: None,
                     rhyme: String::from("an"),
-                    tone: Some(1)
+                    tone: Some(1),
+                    ..Default::default()
                 }),
                 Token::Punctuation(String::from("=")),
                 Token::Syllable(
@@ -409,7 +444,8 @@ mod cli_test {
                     full: "an1".to_string(),
                     initial: None,
                     rhyme: String::from("an"),
-                    tone: Some(1)
+                    tone: Some(1),
+                    ..Default::default()
                 }),
                 Token::Punctuation("?".to_string()),
                 Token::Punctuation("\"".to_string()),
@@ -854,6 +890,21 @@ mod core_test {
         assert_eq!(output, "liú lüè jiǔ de".to_string());
     }
 
+    #[test]
+    fn test_convertion_ipaletter() {
+        let builder = Siphon::new("zhe4 shi4 yi2ge0 ce4shi4").format(Format::IPAToneLetter);
+        let tokens: Vec<Token> = builder.tokenize().unwrap_or_else(|e| {
+            eprintln!("Tokenization failed: {}", e);
+            vec![]
+        });
+        let output: String = builder.transform(tokens).unwrap_or_else(|e| {
+            eprintln!("Transformation failed: {}", e);
+            String::new()
+        });
+
+        assert_eq!(output, "tʂɤ˥˩ ʂʅ˥˩ i˧˥kɤ꜊ tsʰɤ˥˩ʂʅ˥˩".to_string());
+    }
+
     #[test]
     fn test_convertion_ipatex() {
         let builder = Siphon::new("liu2 lve4")
@@ -871,3 +922,1322 @@ mod core_test {
         assert_eq!(output, r"ljɤw\UP{35} lɥœ\UP{51}".to_string());
     }
 }
+
+#[cfg(test)]
+mod diacritic_input_test {
+    use siphon::{cli::format::Format, Siphon, Token};
+
+    #[test]
+    fn test_diacritic_input_tokenization() {
+        let builder = Siphon::new("zhōngguó lǘ");
+        let tokens: Vec<Token> = builder.tokenize().unwrap();
+        let tones: Vec<Option<usize>> = tokens
+            .iter()
+            .filter_map(|tok| match tok {
+                Token::Syllable(syl) => Some(syl.tone),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(tones, vec![Some(1), Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn test_diacritic_to_every_format() {
+        let test_cases = [
+            (Format::PinyinDiacritic, "zhōngguó lüè"),
+            (Format::PinyinSuperscript, "zhong⁵⁵guo³⁵ lüe⁵¹"),
+            (Format::PinyinLaTeX, r"zhong\UP{55}guo\UP{35} lüe\UP{51}"),
+            (Format::IPASuperscript, "tʂʊŋ⁵⁵kwʌ³⁵ lɥœ⁵¹"),
+            (Format::IPALaTeX, r"tʂʊŋ\UP{55}kwʌ\UP{35} lɥœ\UP{51}"),
+        ];
+
+        for (format, expected) in test_cases {
+            let builder = Siphon::new("zhōngguó lüè").format(format).wrapper("UP");
+            let tokens: Vec<Token> = builder.tokenize().unwrap();
+            assert_eq!(builder.transform(tokens).unwrap(), expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod numbered_test {
+    use super::convert;
+    use siphon::{
+        cli::format::{Format, NeutralTone, Umlaut},
+        Siphon,
+    };
+
+    #[test]
+    fn test_convertion_numbered() {
+        let builder =
+            Siphon::new("Zhōngguó de lǜ xué, nü3 ju4 xi1'an1").format(Format::PinyinNumbered);
+
+        assert_eq!(convert(builder), "zhong1guo2 de5 lv4 xue2, nv3 ju4 xi1an1");
+    }
+
+    #[test]
+    fn test_numbered_options() {
+        let test_cases = [
+            (NeutralTone::Five, Umlaut::V, "lv4 le5"),
+            (NeutralTone::Zero, Umlaut::V, "lv4 le0"),
+            (NeutralTone::Five, Umlaut::UColon, "lu:4 le5"),
+            (NeutralTone::Zero, Umlaut::Umlaut, "lu\u{308}4 le0"),
+        ];
+
+        for (neutral_tone, umlaut, expected) in test_cases {
+            let builder = Siphon::new("lv4 le5")
+                .format(Format::PinyinNumbered)
+                .neutral_tone(neutral_tone)
+                .umlaut(umlaut);
+            assert_eq!(convert(builder), expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod zhuyin_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon, SYLLABLE_INVENTORY};
+
+    #[test]
+    fn test_convertion_zhuyin() {
+        assert_eq!(
+            convert(Siphon::new("zhong1guo2").format(Format::Zhuyin)),
+            "ㄓㄨㄥ ㄍㄨㄛˊ"
+        );
+        assert_eq!(
+            convert(Siphon::new("ni3 hao3 ma5?").format(Format::Zhuyin)),
+            "ㄋㄧˇ ㄏㄠˇ ˙ㄇㄚ?"
+        );
+        assert_eq!(
+            convert(Siphon::new("yi1 you3 wen4 yue4 yong3 jiu3 xue2").format(Format::Zhuyin)),
+            "ㄧ ㄧㄡˇ ㄨㄣˋ ㄩㄝˋ ㄩㄥˇ ㄐㄧㄡˇ ㄒㄩㄝˊ"
+        );
+    }
+
+    #[test]
+    fn test_zhuyin_empty_rhymes() {
+        assert_eq!(
+            convert(Siphon::new("zhi1 chi1 shi1 ri4 zi4 ci2 si1").format(Format::Zhuyin)),
+            "ㄓ ㄔ ㄕ ㄖˋ ㄗˋ ㄘˊ ㄙ"
+        );
+    }
+
+    #[test]
+    fn test_zhuyin_input() {
+        let test_cases = [
+            (Format::PinyinDiacritic, "zhōng guó de huàr, nǚ jù yǒu"),
+            (
+                Format::PinyinNumbered,
+                "zhong1 guo2 de5 huar4, nv3 ju4 you3",
+            ),
+            (
+                Format::IPASuperscript,
+                "tʂʊŋ⁵⁵ kwʌ³⁵ tɤ⁰ xwɐʵ⁵¹, ny²¹⁴ tɕy⁵¹ jɤw²¹⁴",
+            ),
+            (
+                Format::Zhuyin,
+                "ㄓㄨㄥ ㄍㄨㄛˊ ˙ㄉㄜ ㄏㄨㄚㄦˋ, ㄋㄩˇ ㄐㄩˋ ㄧㄡˇ",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            let builder =
+                Siphon::new("ㄓㄨㄥ ㄍㄨㄛˊ ˙ㄉㄜ ㄏㄨㄚㄦˋ, ㄋㄩˇ ㄐㄩˋ ㄧㄡˇ").format(format);
+            assert_eq!(convert(builder), expected);
+        }
+    }
+
+    #[test]
+    fn test_zhuyin_round_trip() {
+        // every legal syllable, the interjections (i.e. ㄝ, ㄇ, ㄏㄫ) included
+        for syllable in SYLLABLE_INVENTORY.iter() {
+            let pinyin = format!("{syllable}4");
+            let zhuyin = convert(Siphon::new(&pinyin).format(Format::Zhuyin));
+            assert_eq!(
+                convert(Siphon::new(&zhuyin).format(Format::PinyinNumbered)),
+                convert(Siphon::new(&pinyin).format(Format::PinyinNumbered)),
+                "{pinyin} -> {zhuyin}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_zhuyin_interjection_input() {
+        let builder = Siphon::new("ㄝˋ ㄇˊ ㄋˇ ㄫˊ ㄏㄇ ˙ㄏㄫ").format(Format::PinyinDiacritic);
+        assert_eq!(convert(builder), "ề ḿ ň ńg hm̄ hng");
+
+        let builder = Siphon::new("ㄅㄇ").format(Format::PinyinDiacritic);
+        assert!(builder.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_zhuyin_input_invalid() {
+        let builder = Siphon::new("ㄍ").format(Format::IPASuperscript);
+        assert!(builder.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_zhuyin_erhua() {
+        assert_eq!(
+            convert(Siphon::new("er2 huar4 zhir1 nar3").format(Format::Zhuyin)),
+            "ㄦˊ ㄏㄨㄚㄦˋ ㄓㄦ ㄋㄚㄦˇ"
+        );
+    }
+}
+
+#[cfg(test)]
+mod wade_giles_test {
+    use super::convert_nfc;
+    use siphon::{
+        cli::format::{Format, ToneDigits},
+        Siphon,
+    };
+
+    #[test]
+    fn test_convertion_wade_giles() {
+        let builder = Siphon::new("chi1 xue2 ri4 zi3 ci4 si1 zhe4 ge1 guo2 er2 xi1'an1")
+            .format(Format::WadeGiles);
+
+        assert_eq!(
+            convert_nfc(builder),
+            "ch'ih¹ hsüeh² jih⁴ tzŭ³ tz'ŭ⁴ ssŭ¹ chê⁴ ko¹ kuo² êrh² hsi¹-an¹"
+        );
+    }
+
+    #[test]
+    fn test_wade_giles_without_initial() {
+        let builder = Siphon::new("yi1 you3 wen4 yu2 yue4 wo3 de5").format(Format::WadeGiles);
+
+        assert_eq!(convert_nfc(builder), "i¹ yu³ wên⁴ yü² yüeh⁴ wo³ tê");
+    }
+
+    #[test]
+    fn test_wade_giles_inline_digits() {
+        let builder = Siphon::new("mao2 ze2dong1")
+            .format(Format::WadeGiles)
+            .tone_digits(ToneDigits::Inline);
+
+        assert_eq!(convert_nfc(builder), "mao2 tsê2tung1");
+    }
+
+    #[test]
+    fn test_wade_giles_uei() {
+        let builder = Siphon::new("gui4 kui4 hui4 shui3 tui1 rui4").format(Format::WadeGiles);
+
+        assert_eq!(convert_nfc(builder), "kuei⁴ k'uei⁴ hui⁴ shui³ t'ui¹ jui⁴");
+    }
+}
+
+#[cfg(test)]
+mod yale_test {
+    use super::convert_nfc;
+    use siphon::{cli::format::Format, Siphon};
+    use unicode_normalization::UnicodeNormalization;
+
+    #[test]
+    fn test_convertion_yale() {
+        assert_eq!(
+            convert_nfc(
+                Siphon::new("xue2 xi2 ju1 qu4 dian3 jiu3 dui4 guo2 bo1 dong1").format(Format::Yale)
+            ),
+            "sywé syí jyū chyù dyǎn jyǒu dwèi gwó bwō dūng"
+        );
+        assert_eq!(
+            convert_nfc(Siphon::new("yi1 you3 wen4 yue4 yuan2 wo3 de5").format(Format::Yale)),
+            "yī yǒu wèn ywè ywán wǒ de"
+        );
+    }
+
+    #[test]
+    fn test_yale_empty_rhymes() {
+        assert_eq!(
+            convert_nfc(Siphon::new("zhi1 chi2 shi3 ri4 zi4 ci2 si1").format(Format::Yale)),
+            "jr̄ chŕ shř r̀ dz̀ tsź sz̄".nfc().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn test_yale_erhua() {
+        assert_eq!(
+            convert_nfc(Siphon::new("er4 nar3 yi4dianr3 huar1 shir4 yangr4").format(Format::Yale)),
+            "èr nǎr yìdyǎr hwār shèr yàngr"
+        );
+    }
+}
+
+#[cfg(test)]
+mod gwoyeu_romatzyh_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon};
+
+    #[test]
+    fn test_gwoyeu_romatzyh_tones() {
+        assert_eq!(
+            convert(Siphon::new("hao1 hao2 hao3 hao4").format(Format::GwoyeuRomatzyh)),
+            "hau haur hao haw"
+        );
+        assert_eq!(
+            convert(Siphon::new("ma1 ma2 ma3 ma4 ma5").format(Format::GwoyeuRomatzyh)),
+            "mha ma maa mah .ma"
+        );
+        assert_eq!(
+            convert(Siphon::new("qu4 xiao3 xue2 zhong1guo2").format(Format::GwoyeuRomatzyh)),
+            "chiuh sheau shyue jonggwo"
+        );
+        assert_eq!(
+            convert(Siphon::new("zhi1 chi2 shi3 ri4 zi4").format(Format::GwoyeuRomatzyh)),
+            "jy chyr shyy ryh tzyh"
+        );
+    }
+
+    #[test]
+    fn test_gwoyeu_romatzyh_zero_initial() {
+        assert_eq!(
+            convert(Siphon::new("yi1 yi2 yi3 yi4").format(Format::GwoyeuRomatzyh)),
+            "i yi yii yih"
+        );
+        assert_eq!(
+            convert(Siphon::new("you3 yu3 wo3 wan3").format(Format::GwoyeuRomatzyh)),
+            "yeou yeu woo woan"
+        );
+        assert_eq!(
+            convert(Siphon::new("yue4 wai4 wang4 ying4").format(Format::GwoyeuRomatzyh)),
+            "yueh way wanq yinq"
+        );
+    }
+
+    #[test]
+    fn test_gwoyeu_romatzyh_erhua() {
+        assert_eq!(
+            convert(Siphon::new("er2 er4 huar1 huar4 nar3").format(Format::GwoyeuRomatzyh)),
+            "erl ell hual huall naal"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tongyong_mps2_test {
+    use super::convert_nfc;
+    use siphon::{cli::format::Format, Siphon};
+    use unicode_normalization::UnicodeNormalization;
+
+    #[test]
+    fn test_convertion_tongyong() {
+        assert_eq!(
+            convert_nfc(
+                Siphon::new("zhong1guo2 xi1 qi4 xue2 lü4 feng1 liu2 wen1").format(Format::Tongyong)
+            ),
+            "jhongguó si cì syué lyù fong lióu wun"
+        );
+        assert_eq!(
+            convert_nfc(Siphon::new("zhi1 chi2 ri4 zi4 si1").format(Format::Tongyong)),
+            "jhih chíh rìh zìh sih"
+        );
+        assert_eq!(
+            convert_nfc(Siphon::new("de5 xi1'an1").format(Format::Tongyong)),
+            "de\u{307} si'an".nfc().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn test_convertion_mps2() {
+        assert_eq!(
+            convert_nfc(
+                Siphon::new("zhong1guo2 xi1 ju1 xue2 lü4 dun4 xiong2 de5").format(Format::MPS2)
+            ),
+            "jūngguó shī jiū shiué liù duèn shiúng de"
+        );
+        assert_eq!(
+            convert_nfc(Siphon::new("zhi1 chi2 ri4 zi4 si1 tai2'wan1").format(Format::MPS2)),
+            "jr̄ chŕ r̀ tz̀ sz̄ tái-wān".nfc().collect::<String>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod sampa_test {
+    use siphon::{
+        cli::{format::Format, syllable::Syllable},
+        error::SiphonError,
+        Siphon, Token, INITIAL_MAP, RHYME_MAP,
+    };
+
+    #[test]
+    fn test_every_ipa_symbol_has_sampa() {
+        let syllable = Syllable::default();
+
+        for format in [Format::XSampa, Format::SampaC] {
+            for ipa in INITIAL_MAP.values().chain(RHYME_MAP.values()) {
+                let result = syllable.ipa_to_sampa(ipa, &format);
+                assert!(
+                    result.is_ok(),
+                    "No {:?} for {}: {:?}",
+                    format,
+                    ipa,
+                    result.err()
+                );
+                assert!(result.unwrap().is_ascii());
+            }
+        }
+    }
+
+    #[test]
+    fn test_unmapped_ipa_symbol() {
+        let syllable = Syllable::default();
+
+        assert!(matches!(
+            syllable.ipa_to_sampa("ʔa", &Format::XSampa),
+            Err(SiphonError::SampaNotFound(symbol)) if symbol == "ʔ"
+        ));
+    }
+
+    #[test]
+    fn test_convertion_sampa() {
+        let test_cases = [
+            (
+                Format::XSampa,
+                "ts`7_51 s`z`=_51, s\\H9_35 ts\\_hy_51 ts_h7_0",
+            ),
+            (Format::SampaC, "ts`7_51 s`i`_51, s\\H9_35 ts\\hy_51 tsh7_0"),
+        ];
+
+        for (format, expected) in test_cases {
+            let builder = Siphon::new("zhe4 shi4, xue2 qu4 ce5").format(format);
+            let tokens: Vec<Token> = builder.tokenize().unwrap();
+            assert_eq!(builder.transform(tokens).unwrap(), expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tipa_test {
+    use siphon::{
+        cli::{format::Format, syllable::Syllable},
+        error::SiphonError,
+        Siphon, Token, INITIAL_MAP, RHYME_MAP,
+    };
+
+    #[test]
+    fn test_every_ipa_symbol_has_tipa() {
+        let syllable = Syllable::default();
+
+        for ipa in INITIAL_MAP.values().chain(RHYME_MAP.values()) {
+            let result = syllable.ipa_to_tipa(ipa);
+            assert!(result.is_ok(), "No TIPA for {}: {:?}", ipa, result.err());
+            assert!(result.unwrap().is_ascii());
+        }
+    }
+
+    #[test]
+    fn test_unmapped_ipa_symbol() {
+        let syllable = Syllable::default();
+
+        assert!(matches!(
+            syllable.ipa_to_tipa("ʔa"),
+            Err(SiphonError::TipaNotFound(symbol)) if symbol == "ʔ"
+        ));
+    }
+
+    #[test]
+    fn test_convertion_tipa() {
+        let builder = Siphon::new("zhe4 shi4, yang4 er2").format(Format::IPATipa);
+        let tokens: Vec<Token> = builder.tokenize().unwrap();
+        assert_eq!(
+            builder.transform(tokens).unwrap(),
+            r"t\textrtails{}\textramshorns{}\textsuperscript{51} \textrtails{}\textsyllabic{\textrtailz}\textsuperscript{51}, j\textscripta{}\ng{}\textsuperscript{51} \textramshorns{}\textrhoticity{}\textsuperscript{35}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod ruby_test {
+    use siphon::{cli::format::Format, error::SiphonError, Siphon};
+
+    #[test]
+    fn test_ruby_groups() {
+        let builder = Siphon::new("我(wo3)爱(ai4)中国（zhong1guo2）。").ruby(true);
+        assert_eq!(
+            builder.transform_ruby().unwrap(),
+            "<ruby>我<rt>wǒ</rt></ruby><ruby>爱<rt>ài</rt></ruby><ruby>中<rt>zhōng</rt>国<rt>guó</rt></ruby>。"
+        );
+    }
+
+    #[test]
+    fn test_ruby_group_counts_every_hanzi() {
+        // the whole run of Hanzi before a group is annotated
+        for (text, hanzi_count) in [("我爱中国(zhong1guo2)。", 4), ("中国人(zhong1guo2)", 3)]
+        {
+            let result = Siphon::new(text).ruby(true).transform_ruby();
+            assert!(
+                matches!(result, Err(SiphonError::RubyMismatch(_, count, _, 2)) if count == hanzi_count),
+                "{text}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ruby_parallel_lines() {
+        let builder = Siphon::new("中国，你好\nzhong1guo2, ni3 hao3")
+            .format(Format::IPASuperscript)
+            .ruby(true);
+        assert_eq!(
+            builder.transform_ruby().unwrap(),
+            "<ruby>中<rt>tʂʊŋ⁵⁵</rt>国<rt>kwʌ³⁵</rt></ruby>，<ruby>你<rt>ni²¹⁴</rt>好<rt>xɑw²¹⁴</rt></ruby>"
+        );
+    }
+
+    #[test]
+    fn test_ruby_mismatch() {
+        for text in [
+            "中国(zhong1)",
+            "中国(zhong1guo2ren2)",
+            "中国\nzhong1 guo2 ren2",
+        ] {
+            let result = Siphon::new(text).ruby(true).transform_ruby();
+            assert!(
+                matches!(result, Err(SiphonError::RubyMismatch(_, 2, _, _))),
+                "{:?}",
+                result
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod json_test {
+    use serde_json::{json, Value};
+    use siphon::Siphon;
+
+    #[test]
+    fn test_json_token_kinds() {
+        let output: String = Siphon::new("xi1'an1, ni3")
+            .json(true)
+            .transform_json()
+            .unwrap();
+        let records: Value = serde_json::from_str(&output).unwrap();
+
+        let kinds: Vec<&str> = records
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|record| record["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "syllable",
+                "separator",
+                "syllable",
+                "punctuation",
+                "space",
+                "syllable"
+            ]
+        );
+        assert_eq!(records[3], json!({ "kind": "punctuation", "text": "," }));
+    }
+
+    #[test]
+    fn test_json_other() {
+        let output: String = Siphon::new("你 ni3").transform_json().unwrap();
+        let records: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(records[0], json!({ "kind": "other", "text": "你" }));
+    }
+
+    #[test]
+    fn test_json_joined_words() {
+        let output: String = Siphon::new("xi1an1 ni3")
+            .join(true)
+            .transform_json()
+            .unwrap();
+        let records: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(records[1]["rendered"]["dia"], "ān");
+        assert_eq!(records[1]["rendered"]["ipasup"], "an⁵⁵");
+        assert_eq!(records[3]["rendered"]["dia"], "nǐ");
+    }
+
+    #[test]
+    fn test_json_syllable() {
+        let output: String = Siphon::new("jiu3").transform_json().unwrap();
+        let records: Value = serde_json::from_str(&output).unwrap();
+        let syllable: &Value = &records[0];
+
+        assert_eq!(syllable["original"], "jiu3");
+        assert_eq!(syllable["initial"], "j");
+        assert_eq!(syllable["rhyme"], "iu");
+        assert_eq!(syllable["tone"], 3);
+        assert_eq!(syllable["ipa"], "tɕjɤw");
+        assert_eq!(syllable["contour"], "214");
+        assert_eq!(syllable["rendered"]["dia"], "jiǔ");
+        assert_eq!(syllable["rendered"]["zhuyin"], "ㄐㄧㄡˇ");
+        assert_eq!(syllable["rendered"]["wade-giles"], "chiu³");
+        assert_eq!(syllable["rendered"].as_object().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_json_interjection() {
+        let output: String = Siphon::new("m2, ni3").transform_json().unwrap();
+        let records: Value = serde_json::from_str(&output).unwrap();
+        let syllable: &Value = &records[0];
+
+        assert_eq!(syllable["rhyme"], "m");
+        assert_eq!(syllable["ipa"], "m̩");
+        assert_eq!(syllable["rendered"]["zhuyin"], "ㄇˊ");
+        assert_eq!(syllable["rendered"]["wade-giles"], "m²");
+        assert_eq!(syllable["rendered"].as_object().unwrap().len(), 16);
+        assert_eq!(records[3]["rendered"]["dia"], "nǐ");
+    }
+}
+
+#[cfg(test)]
+mod sandhi_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon};
+
+    #[test]
+    fn test_third_tone_sandhi() {
+        let test_cases = [
+            ("ni3 hao3", "ni³⁵ xɑw²¹⁴"),
+            ("ni3 chi1", "ni²¹ tʂʰʅ⁵⁵"),
+            ("hao3, hao3", "xɑw²¹⁴, xɑw²¹⁴"),
+            // (AB)C and A(BC)
+            ("zhan3lan3 guan3", "tʂan³⁵lan³⁵ kwan²¹⁴"),
+            ("xiao3 lao3hu3", "ɕjɑw²¹ lɑw³⁵xu²¹⁴"),
+            ("xiao3'lao3hu3", "ɕjɑw²¹lɑw³⁵xu²¹⁴"),
+            ("wo3 hen3 hao3", "wʌ³⁵ xən³⁵ xɑw²¹⁴"),
+        ];
+
+        for (text, expected) in test_cases {
+            assert_eq!(
+                convert(
+                    Siphon::new(text)
+                        .format(Format::IPASuperscript)
+                        .sandhi(true)
+                ),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_sandhi_is_opt_in() {
+        assert_eq!(
+            convert(
+                Siphon::new("ni3 hao3")
+                    .format(Format::IPASuperscript)
+                    .sandhi(false)
+            ),
+            "ni²¹⁴ xɑw²¹⁴"
+        );
+    }
+
+    #[test]
+    fn test_underlying_tone_kept() {
+        assert_eq!(
+            convert(
+                Siphon::new("ni3 hao3")
+                    .format(Format::PinyinDiacritic)
+                    .sandhi(true)
+            ),
+            "nǐ hǎo"
+        );
+        assert_eq!(
+            convert(
+                Siphon::new("ni3 hao3")
+                    .format(Format::PinyinNumbered)
+                    .sandhi(true)
+            ),
+            "ni3 hao3"
+        );
+    }
+}
+
+#[cfg(test)]
+mod yi_bu_test {
+    use super::convert;
+    use siphon::{
+        cli::format::{Format, SurfaceTones},
+        Siphon,
+    };
+
+    #[test]
+    fn test_yi_bu_sandhi() {
+        let test_cases = [
+            ("yi1 ding4", "i³⁵ tiŋ⁵¹"),
+            ("yi1 tian1", "i⁵¹ tʰjɛn⁵⁵"),
+            ("yi1 nian2", "i⁵¹ njɛn³⁵"),
+            ("yi1 qi3", "i⁵¹ tɕʰi²¹⁴"),
+            ("bu4 shi4", "pu³⁵ ʂʅ⁵¹"),
+            ("bu4 hao3", "pu⁵¹ xɑw²¹⁴"),
+            ("bu4 yi1yang4", "pu⁵¹ i³⁵jɑŋ⁵¹"),
+            // the neutral ge5 stands for ge4
+            ("yi1 ge5", "i³⁵ kɤ⁰"),
+            // nothing follows in the phrase
+            ("shi2yi1, bu4", "ʂʅ³⁵i⁵⁵, pu⁵¹"),
+        ];
+
+        for (text, expected) in test_cases {
+            assert_eq!(
+                convert(
+                    Siphon::new(text)
+                        .format(Format::IPASuperscript)
+                        .yi_bu(true)
+                        .surface_tones(SurfaceTones::Ipa)
+                ),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_keep_marker() {
+        assert_eq!(
+            convert(
+                Siphon::new("di4 yi1* ming2")
+                    .format(Format::IPASuperscript)
+                    .yi_bu(true)
+                    .surface_tones(SurfaceTones::Ipa)
+            ),
+            "ti⁵¹ i⁵⁵ miŋ³⁵"
+        );
+        assert_eq!(
+            convert(
+                Siphon::new("yi1* er4 san1")
+                    .format(Format::PinyinDiacritic)
+                    .yi_bu(true)
+                    .surface_tones(SurfaceTones::Pinyin)
+            ),
+            "yī èr sān"
+        );
+    }
+
+    #[test]
+    fn test_surface_tones_in_pinyin() {
+        let text = "yi1 ding4, bu4 shi4";
+        assert_eq!(
+            convert(
+                Siphon::new(text)
+                    .format(Format::PinyinDiacritic)
+                    .yi_bu(true)
+                    .surface_tones(SurfaceTones::Ipa)
+            ),
+            "yī dìng, bù shì"
+        );
+        assert_eq!(
+            convert(
+                Siphon::new(text)
+                    .format(Format::PinyinDiacritic)
+                    .yi_bu(true)
+                    .surface_tones(SurfaceTones::Pinyin)
+            ),
+            "yí dìng, bú shì"
+        );
+    }
+
+    #[test]
+    fn test_surface_tones_in_pinyin_only_yi_bu() {
+        assert_eq!(
+            convert(
+                Siphon::new("ni3 hao3, yi1 ding4")
+                    .format(Format::PinyinDiacritic)
+                    .sandhi(true)
+                    .yi_bu(true)
+                    .surface_tones(SurfaceTones::Pinyin)
+            ),
+            "nǐ hǎo, yí dìng"
+        );
+    }
+
+    #[test]
+    fn test_keep_marker_without_yi_bu() {
+        assert_eq!(
+            convert(Siphon::new("di4 yi1* ming2").format(Format::PinyinDiacritic)),
+            "dì yī* míng"
+        );
+    }
+}
+
+#[cfg(test)]
+mod neutral_pitch_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon};
+
+    #[test]
+    fn test_contextual_neutral_pitch() {
+        let test_cases = [
+            ("ta1 de5", "tʰɑ⁵⁵ tɤ²"),
+            ("lai2 le5", "laj³⁵ lɤ³"),
+            ("ni3 de5", "ni²¹⁴ tɤ⁴"),
+            ("kan4 le5", "kʰan⁵¹ lɤ¹"),
+            ("wo3 men5 de5", "wʌ²¹⁴ mən⁴ tɤ⁴"),
+            // nothing before in the phrase
+            ("de5, ma1ma5", "tɤ⁰, mɑ⁵⁵mɑ²"),
+        ];
+
+        for (text, expected) in test_cases {
+            let builder = Siphon::new(text)
+                .format(Format::IPASuperscript)
+                .neutral_pitch(true);
+            assert_eq!(convert(builder), expected);
+        }
+    }
+
+    #[test]
+    fn test_neutral_pitch_after_sandhi() {
+        let builder = Siphon::new("yi1 ge5")
+            .format(Format::IPAToneLetter)
+            .yi_bu(true)
+            .neutral_pitch(true);
+        assert_eq!(convert(builder), "i˧˥ kɤ˧");
+    }
+
+    #[test]
+    fn test_neutral_pitch_is_opt_in() {
+        let builder = Siphon::new("ta1 de5").format(Format::IPASuperscript);
+        assert_eq!(convert(builder), "tʰɑ⁵⁵ tɤ⁰");
+    }
+}
+
+#[cfg(test)]
+mod erhua_test {
+    use super::convert;
+    use siphon::{
+        cli::format::{Erhua, Format},
+        Siphon,
+    };
+
+    #[test]
+    fn test_erhua_merge() {
+        let test_cases = [
+            ("hua1 er5", "xwɐʵ⁵⁵"),
+            ("hua1'er", "xwɐʵ⁵⁵"),
+            ("yi1dian3 er0", "i⁵⁵tjɐʵ²¹⁴"),
+            // the apical rhyme of zhi, chi, shi, ri
+            ("shi4 er5", "ʂɚ⁵¹"),
+            ("zhi1 er5", "tʂɚ⁵⁵"),
+            // er as a word of its own
+            ("nü3 er2", "ny²¹⁴ ɤʵ³⁵"),
+            // nothing to fold into
+            ("er5, hua1", "ɤʵ⁰, xwɑ⁵⁵"),
+        ];
+
+        for (text, expected) in test_cases {
+            assert_eq!(
+                convert(
+                    Siphon::new(text)
+                        .format(Format::IPASuperscript)
+                        .erhua(Erhua::Merge)
+                ),
+                expected
+            );
+        }
+        assert_eq!(
+            convert(
+                Siphon::new("hua1 er5")
+                    .format(Format::PinyinDiacritic)
+                    .erhua(Erhua::Merge)
+            ),
+            "huār"
+        );
+    }
+
+    #[test]
+    fn test_erhua_keep_pinyin() {
+        assert_eq!(
+            convert(
+                Siphon::new("hua1 er5")
+                    .format(Format::PinyinDiacritic)
+                    .erhua(Erhua::KeepPinyin)
+            ),
+            "huā er"
+        );
+        assert_eq!(
+            convert(
+                Siphon::new("hua1 er5")
+                    .format(Format::Zhuyin)
+                    .erhua(Erhua::KeepPinyin)
+            ),
+            "ㄏㄨㄚㄦ"
+        );
+    }
+
+    #[test]
+    fn test_erhua_separate() {
+        assert_eq!(
+            convert(
+                Siphon::new("hua1 er5")
+                    .format(Format::IPASuperscript)
+                    .erhua(Erhua::Separate)
+            ),
+            "xwɑ⁵⁵ ɤʵ⁰"
+        );
+    }
+}
+
+#[cfg(test)]
+mod interjection_test {
+    use super::convert;
+    use siphon::{
+        cli::format::{Format, Umlaut},
+        Siphon,
+    };
+
+    #[test]
+    fn test_interjection_pinyin() {
+        let text = "ê1 ê2 ê3 ê4 m2 m4 n2 n3 n4 ng2 ng3 ng4 hm5 hng2";
+        let test_cases = [
+            (
+                Format::PinyinDiacritic,
+                "ê̄ ế ê̌ ề ḿ m̀ ń ň ǹ ńg ňg ǹg hm hńg",
+            ),
+            (
+                Format::PinyinNumbered,
+                "e^1 e^2 e^3 e^4 m2 m4 n2 n3 n4 ng2 ng3 ng4 hm5 hng2",
+            ),
+            (
+                Format::IPASuperscript,
+                "ɛ⁵⁵ ɛ³⁵ ɛ²¹⁴ ɛ⁵¹ m̩³⁵ m̩⁵¹ n̩³⁵ n̩²¹⁴ n̩⁵¹ ŋ̍³⁵ ŋ̍²¹⁴ ŋ̍⁵¹ xm̩⁰ xŋ̍³⁵",
+            ),
+            (
+                Format::XSampa,
+                "E_55 E_35 E_214 E_51 m=_35 m=_51 n=_35 n=_214 n=_51 N=_35 N=_214 N=_51 xm=_0 xN=_35",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(convert(Siphon::new(text).format(format)), expected);
+        }
+    }
+
+    #[test]
+    fn test_interjection_tone_marks() {
+        let builder = Siphon::new("ế ḿ ň ǹg hm̀ hn̄g ê̄").format(Format::PinyinNumbered);
+        assert_eq!(convert(builder), "e^2 m2 n3 ng4 hm4 hng1 e^1");
+
+        let builder = Siphon::new("ê4")
+            .format(Format::PinyinNumbered)
+            .umlaut(Umlaut::Umlaut);
+        assert_eq!(convert(builder), "ê4");
+    }
+
+    #[test]
+    fn test_interjection_romanizations() {
+        let text = "ê4 m2 n3 ng2 hm1 hng5";
+        let test_cases = [
+            (Format::Zhuyin, "ㄝˋ ㄇˊ ㄋˇ ㄫˊ ㄏㄇ ˙ㄏㄫ"),
+            (Format::WadeGiles, "eh⁴ m² n³ ng² hm¹ hng"),
+            (Format::Yale, "ề ḿ ň ńg hm̄ hng"),
+            (Format::GwoyeuRomatzyh, "è m n ng hm .hng"),
+            (Format::Tongyong, "ề ḿ ň ńg hm hng"),
+            (Format::MPS2, "ề ḿ ň ńg hm̄ hng"),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(convert(Siphon::new(text).format(format)), expected);
+        }
+    }
+
+    #[test]
+    fn test_interjection_next_to_syllables() {
+        let builder = Siphon::new("ng4, ni3 men2 hen3 hao3").format(Format::IPASuperscript);
+        assert_eq!(convert(builder), "ŋ̍⁵¹, ni²¹⁴ mən³⁵ xən²¹⁴ xɑw²¹⁴");
+    }
+}
+
+#[cfg(test)]
+mod inventory_test {
+    use super::try_convert;
+    use clap::ValueEnum;
+    use siphon::{cli::format::Format, error::SiphonError, Siphon, SYLLABLE_INVENTORY};
+
+    #[test]
+    fn test_every_legal_syllable_converts() {
+        for format in Format::value_variants() {
+            // the interjections are spelled without erhua
+            let text: Vec<String> = SYLLABLE_INVENTORY
+                .iter()
+                .flat_map(
+                    |syllable| match ["ê", "m", "n", "ng", "hm", "hng"].contains(syllable) {
+                        true => vec![format!("{syllable}1")],
+                        false => vec![format!("{syllable}1"), format!("{syllable}r4")],
+                    },
+                )
+                .collect();
+
+            let builder = Siphon::new(&text.join(" "))
+                .format(format.clone())
+                .strict(true);
+            let result = try_convert(builder);
+            assert!(result.is_ok(), "{format:?}: {result:?}");
+        }
+    }
+
+    #[test]
+    fn test_illegal_syllables() {
+        for text in ["gi1", "fong2", "biu3", "zhi1 kü4", "ㄍㄧ"] {
+            let result = try_convert(Siphon::new(text).strict(true));
+            assert!(
+                matches!(&result, Err(SiphonError::IllegalSyllable(_))),
+                "{text}: {result:?}"
+            );
+        }
+
+        assert!(matches!(
+            try_convert(Siphon::new("zhong1 biu3").strict(true)),
+            Err(SiphonError::IllegalSyllable(syllable)) if syllable == "biu3"
+        ));
+    }
+
+    #[test]
+    fn test_legal_spellings() {
+        let builder = Siphon::new("lv4 lue4 nve4 ju2 huar1 er2 ng2 hm ㄉㄧㄡ").strict(true);
+        assert_eq!(
+            try_convert(builder).unwrap(),
+            "lu\u{308}\u{300} lu\u{308}è nu\u{308}è jú huār ér ńg hm diū"
+        );
+    }
+
+    #[test]
+    fn test_strict_is_opt_in() {
+        assert!(try_convert(Siphon::new("biu3")).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod segment_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon, Token};
+
+    fn syllables(text: &str) -> Vec<String> {
+        Siphon::new(text)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Syllable(syl) => Some(syl.full),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_segment_unspaced_pinyin() {
+        assert_eq!(
+            syllables("xianzaiwomenqubeijing"),
+            ["xian", "zai", "wo", "men", "qu", "bei", "jing"]
+        );
+        assert_eq!(syllables("Xiànzài wǒmen"), ["Xiàn", "zài", "wǒ", "men"]);
+        assert_eq!(syllables("yidianr"), ["yi", "dianr"]);
+    }
+
+    #[test]
+    fn test_segment_apostrophe_rule() {
+        assert_eq!(syllables("xian"), ["xian"]);
+        assert_eq!(syllables("xi'an"), ["xi", "an"]);
+        assert_eq!(syllables("fangan"), ["fan", "gan"]);
+        assert_eq!(syllables("fang'an"), ["fang", "an"]);
+        assert_eq!(syllables("huarou"), ["hua", "rou"]);
+    }
+
+    #[test]
+    fn test_segment_tone_digits_are_boundaries() {
+        assert_eq!(syllables("xi1an1"), ["xi1", "an1"]);
+        assert_eq!(
+            convert(Siphon::new("xian4zai4wo3men5").format(Format::IPASuperscript)),
+            "ɕjɛn⁵¹tsaj⁵¹wʌ²¹⁴mən⁰"
+        );
+    }
+
+    #[test]
+    fn test_segment_keeps_spelling() {
+        assert_eq!(
+            convert(Siphon::new("Xiànzài wǒmen qù Běijīng.")),
+            "Xiànzài wǒmen qù Běijīng."
+        );
+    }
+}
+
+#[cfg(test)]
+mod join_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon};
+
+    #[test]
+    fn test_join_apostrophe() {
+        let text = "Tian1an1men2 hai3ou1 pi2ao3 zhong1'guo2 xi1'an1";
+        let test_cases = [
+            (
+                Format::PinyinDiacritic,
+                "Tiān'ānmén hǎi'ōu pí'ǎo zhōngguó xī'ān",
+            ),
+            (
+                Format::PinyinNumbered,
+                "tian1'an1men2 hai3'ou1 pi2'ao3 zhong1guo2 xi1'an1",
+            ),
+            (
+                Format::PinyinSuperscript,
+                "Tian⁵⁵'an⁵⁵men³⁵ hai²¹⁴'ou⁵⁵ pi³⁵'ao²¹⁴ zhong⁵⁵guo³⁵ xi⁵⁵'an⁵⁵",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(
+                convert(Siphon::new(text).format(format).join(true)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_join_other_formats() {
+        let builder = Siphon::new("Tian1an1men2 zhong1'guo2").format(Format::WadeGiles);
+        assert_eq!(convert(builder.clone()), convert(builder.join(true)));
+    }
+
+    #[test]
+    fn test_join_is_opt_in() {
+        assert_eq!(
+            convert(Siphon::new("Tian1an1men2 zhong1'guo2")),
+            "Tiānānmén zhōng'guó"
+        );
+    }
+}
+
+#[cfg(test)]
+mod orthography_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon};
+
+    #[test]
+    fn test_orthography_pinyin() {
+        let text = "^wang2 ^xiao3hong2 shi4 ^bei3jing1ren2. ta1 qu4 ^Tian1an1men2! ni3 ne5? ^jing1-^jin1, shi2-jiu3 shi4ji4";
+        let test_cases = [
+            (
+                Format::PinyinDiacritic,
+                "Wáng Xiǎohóng shì Běijīngrén. Tā qù Tiān'ānmén! Nǐ ne? Jīng-Jīn, shí-jiǔ shìjì",
+            ),
+            (
+                Format::PinyinNumbered,
+                "wang2 xiao3hong2 shi4 bei3jing1ren2. ta1 qu4 tian1'an1men2! ni3 ne5? jing1-jin1, shi2-jiu3 shi4ji4",
+            ),
+            (
+                Format::PinyinSuperscript,
+                "Wang³⁵ Xiao²¹⁴hong³⁵ shi⁵¹ Bei²¹⁴jing⁵⁵ren³⁵. Ta⁵⁵ qu⁵¹ Tian⁵⁵'an⁵⁵men³⁵! Ni²¹⁴ ne⁰? Jing⁵⁵-Jin⁵⁵, shi³⁵-jiu²¹⁴ shi⁵¹ji⁵¹",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(
+                convert(Siphon::new(text).format(format).orthography(true)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_orthography_capitalizes_vowel_syllables() {
+        let builder = Siphon::new("ai4guo2. ou1zhou1").orthography(true);
+        assert_eq!(convert(builder), "Àiguó. Ōuzhōu");
+    }
+
+    #[test]
+    fn test_orthography_other_formats() {
+        let text = "^bei3jing1. ta1";
+        let builder = Siphon::new(text).orthography(true);
+        assert_eq!(
+            convert(builder.clone().format(Format::WadeGiles)),
+            "Pei³ching¹. T'a¹"
+        );
+        assert_eq!(
+            convert(builder.format(Format::IPASuperscript)),
+            "pej²¹⁴tɕiŋ⁵⁵. tʰɑ⁵⁵"
+        );
+    }
+
+    #[test]
+    fn test_orthography_personal_names() {
+        let text = "^^wang2xiao3hong2, ^^ou1yang2xiu1, ^^zhu1ge3liang4 he2 ^^si1ma3 ^qian1";
+        assert_eq!(
+            convert(Siphon::new(text).orthography(true)),
+            "Wáng Xiǎohóng, Ōuyáng Xiū, Zhūgě Liàng hé Sīmǎ Qiān"
+        );
+    }
+
+    #[test]
+    fn test_orthography_approximate_numbers() {
+        let text = "san1 si4 tian1, qi1ba1 ge4, yi1 er4 san1, shi2 san1 si4 ge4";
+        assert_eq!(
+            convert(Siphon::new(text).orthography(true)),
+            "Sān-sì tiān, qī-bā gè, yī èr sān, shí sān sì gè"
+        );
+    }
+
+    #[test]
+    fn test_proper_noun_marker_without_orthography() {
+        assert_eq!(convert(Siphon::new("^wang2 ai4 ta1")), "^wáng ài tā");
+        assert_eq!(convert(Siphon::new("2^ma1 ^^wang2")), "2^mā ^^wáng");
+    }
+}
+
+#[cfg(test)]
+mod other_test {
+    use super::convert;
+    use siphon::{cli::format::Format, Siphon, Token};
+
+    #[test]
+    fn test_other_text_is_kept() {
+        let text = "ni3hao3 (你好); 2024/10  wo3\n\tshi4";
+        let test_cases = [
+            (Format::PinyinDiacritic, "nǐhǎo (你好); 2024/10  wǒ\n\tshì"),
+            (
+                Format::IPASuperscript,
+                "ni²¹⁴xɑw²¹⁴ (你好); 2024/10  wʌ²¹⁴\n\tʂʅ⁵¹",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(convert(Siphon::new(text).format(format)), expected);
+        }
+    }
+
+    #[test]
+    fn test_other_tokens() {
+        let tokens: Vec<Token> = Siphon::new("你 ni3/").tokenize().unwrap();
+        assert_eq!(tokens[0], Token::Other(String::from("你")));
+        assert_eq!(tokens[1], Token::Space);
+        assert_eq!(tokens[3], Token::Other(String::from("/")));
+    }
+
+    #[test]
+    fn test_other_ends_phrase() {
+        let builder = Siphon::new("ni3/hao3")
+            .format(Format::IPASuperscript)
+            .sandhi(true);
+        assert_eq!(convert(builder), "ni²¹⁴/xɑw²¹⁴");
+    }
+}
+
+#[cfg(test)]
+mod punctuation_test {
+    use super::convert;
+    use siphon::{
+        cli::format::{Format, PunctuationWidth},
+        Siphon, Token,
+    };
+
+    #[test]
+    fn test_cjk_punctuation_tokens() {
+        let tokens: Vec<Token> = Siphon::new("ni3，hao3。「ma5」").tokenize().unwrap();
+        let punctuation: Vec<&str> = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Punctuation(p) => Some(p.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(punctuation, ["，", "。", "「", "」"]);
+    }
+
+    #[test]
+    fn test_ideographic_space() {
+        let tokens: Vec<Token> = Siphon::new("ni3\u{3000}hao3").tokenize().unwrap();
+        assert_eq!(tokens[1], Token::Other(String::from("\u{3000}")));
+
+        for width in [PunctuationWidth::Full, PunctuationWidth::Half] {
+            let builder = Siphon::new("你好\u{3000}ni3 hao3。").punctuation(width);
+            assert!(convert(builder).starts_with("你好\u{3000}nǐ hǎo"));
+        }
+    }
+
+    #[test]
+    fn test_punctuation_width() {
+        let text = "ni3 hao3, “ma5”？《shu1》 2.5";
+        let test_cases = [
+            (PunctuationWidth::Keep, "nǐ hǎo, “ma”？《shū》 2.5"),
+            (PunctuationWidth::Full, "nǐ hǎo， “ma”？《shū》 2.5"),
+            (PunctuationWidth::Half, "nǐ hǎo, \"ma\"?\"shū\" 2.5"),
+            (PunctuationWidth::Auto, "nǐ hǎo, \"ma\"?\"shū\" 2.5"),
+        ];
+
+        for (width, expected) in test_cases {
+            assert_eq!(convert(Siphon::new(text).punctuation(width)), expected);
+        }
+    }
+
+    #[test]
+    fn test_punctuation_width_auto() {
+        let builder = Siphon::new("我说：“你好！”ni3 hao3。").punctuation(PunctuationWidth::Auto);
+        assert_eq!(convert(builder), "我说：“你好！”nǐ hǎo.");
+        assert_eq!(
+            convert(
+                Siphon::new("ni3 hao3, \"ma5\"?")
+                    .punctuation(PunctuationWidth::Auto)
+                    .format(Format::Zhuyin)
+            ),
+            "ㄋㄧˇ ㄏㄠˇ， “˙ㄇㄚ”？"
+        );
+    }
+}
+
+#[cfg(test)]
+mod case_test {
+    use super::convert;
+    use siphon::{
+        cli::format::Format,
+        cli::syllable::{Case, Syllable},
+        Siphon,
+    };
+
+    #[test]
+    fn test_case_preserved() {
+        let text = "Bei3jing1 ZHONG1GUO2 BĚIJĪNG Ài LÜ4";
+        let test_cases = [
+            (
+                Format::PinyinDiacritic,
+                "Běijīng ZHŌNGGUÓ BĚIJĪNG Ài L\u{1db}",
+            ),
+            (
+                Format::PinyinNumbered,
+                "bei3jing1 zhong1guo2 bei3jing1 ai4 lv4",
+            ),
+            (
+                Format::WadeGiles,
+                "Pei³ching¹ CHUNG¹KUO² PEI³CHING¹ Ai⁴ LU\u{308}⁴",
+            ),
+            (
+                Format::IPASuperscript,
+                "pej²¹⁴tɕiŋ⁵⁵ tʂʊŋ⁵⁵kwʌ³⁵ pej²¹⁴tɕiŋ⁵⁵ aj⁵¹ ly⁵¹",
+            ),
+            (
+                Format::Zhuyin,
+                "ㄅㄟˇ ㄐㄧㄥ ㄓㄨㄥ ㄍㄨㄛˊ ㄅㄟˇ ㄐㄧㄥ ㄞˋ ㄌㄩˋ",
+            ),
+        ];
+
+        for (format, expected) in test_cases {
+            assert_eq!(convert(Siphon::new(text).format(format)), expected);
+        }
+    }
+
+    #[test]
+    fn test_capital_tone_marks() {
+        let builder = Siphon::new("ĀN ÉR Ǒ Ì Ū Ǘ").format(Format::PinyinNumbered);
+        assert_eq!(convert(builder), "an1 er2 o3 i4 u1 v2");
+
+        let syllable = Syllable::new().onset(Some("L")).rhyme("Ü").tone(Some(2));
+        assert_eq!(syllable.tone_to_diacritics().unwrap(), "Ǘ");
+        let syllable = Syllable::new().rhyme("Ai").tone(Some(4));
+        assert_eq!(syllable.tone_to_diacritics().unwrap(), "Ài");
+    }
+
+    #[test]
+    fn test_case_of() {
+        assert_eq!(Case::of("zhong1"), Case::Lower);
+        assert_eq!(Case::of("Zhong1"), Case::Capital);
+        assert_eq!(Case::of("ZHŌNG"), Case::Upper);
+        assert_eq!(Case::of("Ā"), Case::Capital);
+    }
+}